@@ -1,15 +1,61 @@
+use std::io;
 use std::pin::Pin;
 
 use futures::io::{AsyncRead, AsyncWrite};
+use gio::prelude::*;
+
+/// Splits `stream` (e.g. a `gio::SocketConnection`) into a reader and a
+/// writer. Both halves keep `stream` alive, so it is closed only once both of
+/// them are dropped.
+pub fn split<S: IsA<gio::IOStream>>(stream: &S) -> io::Result<(CompatRead, CompatWrite)> {
+    let stream = stream.upcast_ref::<gio::IOStream>();
+
+    let reader = stream
+        .input_stream()
+        .dynamic_cast::<gio::PollableInputStream>()
+        .ok()
+        .and_then(|input| input.into_async_read().ok())
+        .ok_or_else(|| not_pollable("input"))?;
+
+    let writer = stream
+        .output_stream()
+        .dynamic_cast::<gio::PollableOutputStream>()
+        .ok()
+        .and_then(|output| output.into_async_write().ok())
+        .ok_or_else(|| not_pollable("output"))?;
+
+    Ok((
+        CompatRead {
+            inner: reader,
+            _stream: Some(stream.clone()),
+        },
+        CompatWrite {
+            inner: writer,
+            _stream: Some(stream.clone()),
+        },
+    ))
+}
+
+fn not_pollable(kind: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{} stream is not pollable", kind),
+    )
+}
 
 #[derive(Debug)]
 pub struct CompatWrite {
     inner: gio::OutputStreamAsyncWrite<gio::PollableOutputStream>,
+    /// The stream `inner` belongs to. Dropping it would close `inner`.
+    _stream: Option<gio::IOStream>,
 }
 
 impl From<gio::OutputStreamAsyncWrite<gio::PollableOutputStream>> for CompatWrite {
     fn from(inner: gio::OutputStreamAsyncWrite<gio::PollableOutputStream>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            _stream: None,
+        }
     }
 }
 
@@ -40,11 +86,16 @@ impl AsyncWrite for CompatWrite {
 #[derive(Debug)]
 pub struct CompatRead {
     inner: gio::InputStreamAsyncRead<gio::PollableInputStream>,
+    /// The stream `inner` belongs to. Dropping it would close `inner`.
+    _stream: Option<gio::IOStream>,
 }
 
 impl From<gio::InputStreamAsyncRead<gio::PollableInputStream>> for CompatRead {
     fn from(inner: gio::InputStreamAsyncRead<gio::PollableInputStream>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            _stream: None,
+        }
     }
 }
 
@@ -0,0 +1,97 @@
+//! Compares decoding redraw notifications through `rmpv::Value` (i.e.
+//! `decode_redraw_params`) to decoding them straight from the message's bytes
+//! (i.e. `Redraw::events`).
+//!
+//! Run with `cargo bench --bench redraw`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use nvim_rs::rpc::Message;
+use nvim_rs::types::{decode_redraw_params, Redraw};
+
+/// Roughly a 4K screen's worth of cells.
+const ROWS: i64 = 120;
+const COLS: i64 = 400;
+
+const ITERATIONS: u32 = 50;
+
+/// Encodes a redraw notification that redraws the whole grid, like
+/// neovim does when scrolling a buffer.
+fn redraw_message() -> Vec<u8> {
+    let lines = (0..ROWS)
+        .map(|row| {
+            let cells = (0..COLS)
+                .map(|col| {
+                    let text = ((b'a' + (col % 26) as u8) as char).to_string();
+                    if col % 8 == 0 {
+                        rmpv::Value::Array(vec![text.into(), (col % 40).into()])
+                    } else {
+                        rmpv::Value::Array(vec![text.into()])
+                    }
+                })
+                .chain(std::iter::once(rmpv::Value::Array(vec![
+                    " ".into(),
+                    0.into(),
+                    8.into(),
+                ])))
+                .collect::<Vec<_>>();
+
+            rmpv::Value::Array(vec![1.into(), row.into(), 0.into(), cells.into()])
+        })
+        .collect::<Vec<_>>();
+
+    let grid_line = std::iter::once(rmpv::Value::from("grid_line"))
+        .chain(lines)
+        .collect::<Vec<_>>();
+    let params = rmpv::Value::Array(vec![
+        grid_line.into(),
+        rmpv::Value::Array(vec![
+            "grid_cursor_goto".into(),
+            rmpv::Value::Array(vec![1.into(), 0.into(), 0.into()]),
+        ]),
+        rmpv::Value::Array(vec!["flush".into(), rmpv::Value::Array(vec![])]),
+    ]);
+    let msg = rmpv::Value::Array(vec![2.into(), "redraw".into(), params]);
+
+    let mut buf = vec![];
+    rmpv::encode::write_value(&mut buf, &msg).expect("failed to encode message");
+    buf
+}
+
+fn bench<F: FnMut()>(name: &str, mut f: F) -> Duration {
+    // Warm up.
+    f();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_iter = start.elapsed() / ITERATIONS;
+
+    println!("{:<10} {:>12?} / iter", name, per_iter);
+    per_iter
+}
+
+fn main() {
+    let msg = redraw_message();
+    println!("redraw of {}x{} cells, {} bytes", COLS, ROWS, msg.len());
+
+    let value = bench("value", || {
+        let params = match rmp_serde::from_slice::<Message>(black_box(&msg)) {
+            Ok(Message::Notification(notification)) => notification.params,
+            v => panic!("unexpected message: {:?}", v),
+        };
+        black_box(decode_redraw_params(params).expect("failed to decode"));
+    });
+
+    let bytes = bench("bytes", || {
+        let redraw = Redraw::from_message(black_box(&msg)).expect("not a redraw");
+        black_box(redraw.events().expect("failed to decode"));
+    });
+
+    println!(
+        "bytes is {:.2}x faster",
+        value.as_secs_f64() / bytes.as_secs_f64()
+    );
+}
@@ -0,0 +1,239 @@
+use std::marker::PhantomData;
+
+use futures::prelude::*;
+use serde::de::DeserializeOwned;
+
+use crate::rpc::writer::{to_value, WriteError};
+use crate::{CallError, CallResponse, Client, NvimError};
+
+/// Tuple types a `Batch` can collect its results into. Implemented for
+/// tuples of up to 12 elements.
+pub trait BatchResults: Sized {
+    fn decode(values: Vec<rmpv::Value>) -> Result<Self, CallError>;
+}
+
+/// Appends `U` to the end of a tuple type.
+pub trait Push<U> {
+    type Output;
+}
+
+fn decode_at<T: DeserializeOwned>(
+    index: usize,
+    value: Option<rmpv::Value>,
+) -> Result<T, CallError> {
+    let err = |error| CallError::Batch {
+        index,
+        error: Box::new(error),
+    };
+
+    let value = value.ok_or_else(|| err(CallError::MissingResult))?;
+    rmpv::ext::from_value(value).map_err(|_| err(CallError::DecodeResult))
+}
+
+macro_rules! tuple_impls {
+    ($($name:ident)*) => {
+        impl<$($name,)* U> Push<U> for ($($name,)*) {
+            type Output = ($($name,)* U,);
+        }
+
+        tuple_impls!(@results $($name)*);
+    };
+    (@results $($name:ident)*) => {
+        impl<$($name: DeserializeOwned,)*> BatchResults for ($($name,)*) {
+            #[allow(unused_variables, unused_mut, unused_assignments, clippy::unused_unit)]
+            fn decode(values: Vec<rmpv::Value>) -> Result<Self, CallError> {
+                let mut values = values.into_iter();
+                let mut index = 0;
+                Ok(($({
+                    let value = decode_at::<$name>(index, values.next())?;
+                    index += 1;
+                    value
+                },)*))
+            }
+        }
+    };
+}
+
+tuple_impls!();
+tuple_impls!(A);
+tuple_impls!(A B);
+tuple_impls!(A B C);
+tuple_impls!(A B C D);
+tuple_impls!(A B C D E);
+tuple_impls!(A B C D E F);
+tuple_impls!(A B C D E F G);
+tuple_impls!(A B C D E F G H);
+tuple_impls!(A B C D E F G H I);
+tuple_impls!(A B C D E F G H I J);
+tuple_impls!(A B C D E F G H I J K);
+tuple_impls!(@results A B C D E F G H I J K L);
+
+/// Collection of calls that are sent together. Created with `Client::batch`.
+///
+/// Each call's result type is added to the batch's type, and the results
+/// are returned as a tuple in the order the calls were added:
+///
+/// ```ignore
+/// let (mode, lines) = client
+///     .batch()
+///     .call::<Dictionary, _, _>("nvim_get_mode", args![])
+///     .call::<i64, _, _>("nvim_get_option", args!["lines"])
+///     .atomic()
+///     .await?
+///     .await?;
+/// ```
+#[must_use]
+pub struct Batch<T> {
+    client: Client,
+    calls: Vec<(String, rmpv::Value)>,
+    /// First error encountered when encoding the calls' args.
+    error: Option<WriteError>,
+    _results: PhantomData<fn() -> T>,
+}
+
+impl Client {
+    /// Creates a new, empty batch of calls.
+    pub fn batch(&self) -> Batch<()> {
+        Batch {
+            client: self.clone(),
+            calls: vec![],
+            error: None,
+            _results: PhantomData,
+        }
+    }
+}
+
+impl<T> Batch<T> {
+    /// Add a call to the batch. Its result is decoded to `U`.
+    pub fn call<U, S, V>(self, method: S, args: V) -> Batch<T::Output>
+    where
+        T: Push<U>,
+        S: Into<String>,
+        V: serde::Serialize,
+    {
+        let Batch {
+            client,
+            mut calls,
+            mut error,
+            ..
+        } = self;
+
+        if error.is_none() {
+            match to_value(&args) {
+                Ok(args) => calls.push((method.into(), args)),
+                Err(err) => error = Some(err),
+            }
+        }
+
+        Batch {
+            client,
+            calls,
+            error,
+            _results: PhantomData,
+        }
+    }
+
+    /// Number of calls in the batch.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+}
+
+impl<T> Batch<T>
+where
+    T: BatchResults + 'static,
+{
+    /// Send the calls with a single `nvim_call_atomic` request, so neovim
+    /// runs them without processing any other events in between. Execution
+    /// stops at the first failing call, whose index is reported in
+    /// `CallError::Batch`.
+    pub async fn atomic(self) -> Result<CallResponse<T>, WriteError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let calls = self
+            .calls
+            .into_iter()
+            .map(|(method, args)| rmpv::Value::Array(vec![method.into(), args]))
+            .collect::<Vec<_>>();
+
+        let res = self
+            .client
+            .call::<(Vec<rmpv::Value>, rmpv::Value), _, _>("nvim_call_atomic", (calls,))
+            .await?;
+
+        Ok(async move {
+            let (results, error) = res.await?;
+
+            if !error.is_nil() {
+                return Err(atomic_error(error));
+            }
+
+            T::decode(results)
+        }
+        .boxed())
+    }
+
+    /// Send the calls as separate requests. All the requests are written
+    /// before any of the responses are awaited, so the batch takes a single
+    /// round trip, but unlike with `Batch::atomic`, neovim might process other
+    /// events between the calls and every call is executed regardless of the
+    /// others failing. The first failed call is reported in
+    /// `CallError::Batch`.
+    pub async fn pipelined(self) -> Result<CallResponse<T>, WriteError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let responses = self
+            .calls
+            .iter()
+            .map(|(method, args)| {
+                self.client
+                    .request(self.client.encode_request(method, args)?)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(async move {
+            let mut results = Vec::with_capacity(responses.len());
+            for (index, res) in responses.into_iter().enumerate() {
+                let res = res.await.map_err(|error| CallError::Batch {
+                    index,
+                    error: Box::new(error),
+                })?;
+                // NOTE: Void calls might not have the result set.
+                results.push(res.unwrap_or(rmpv::Value::Nil));
+            }
+
+            T::decode(results)
+        }
+        .boxed())
+    }
+}
+
+/// Decodes the error of `nvim_call_atomic`, which is `[index, type, message]`.
+fn atomic_error(error: rmpv::Value) -> CallError {
+    if let rmpv::Value::Array(parts) = &error {
+        if let [index, kind, message] = parts.as_slice() {
+            if let Some(index) = index.as_u64() {
+                let error = rmpv::Value::Array(vec![kind.clone(), message.clone()]);
+                let error = match NvimError::from_value(&error) {
+                    Some(error) => CallError::Error(error),
+                    None => CallError::DecodeError(error),
+                };
+
+                return CallError::Batch {
+                    index: index as usize,
+                    error: Box::new(error),
+                };
+            }
+        }
+    }
+
+    CallError::DecodeError(error)
+}
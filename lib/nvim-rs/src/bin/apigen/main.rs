@@ -1,27 +1,56 @@
-use quote::quote;
+use quote::{format_ident, quote};
 
 mod types;
 
 use types::ApiMetadata;
 
 fn usage_exit() -> ! {
-    eprintln!("Usage: apigen functions|uievents");
+    eprintln!("Usage: apigen functions API_LEVEL|uievents|keysets");
     std::process::exit(1);
 }
 
-fn functions(res: ApiMetadata) {
-    let functions = res
-        .functions
-        .iter()
-        .filter_map(|function| function.to_tokens());
+/// Generates the bindings for the functions available at the api `level`.
+/// The level is also emitted as `MIN_API_LEVEL`, the api level nvim needs to
+/// support for the bindings to be usable.
+fn functions(res: ApiMetadata, level: i64) {
+    let available = || {
+        res.functions
+            .iter()
+            .filter(move |function| function.available_at(level))
+    };
+
+    let functions = available().map(|function| function.to_tokens(&res.keysets));
+    let methods = res.types.handles().map(|(ty, ext)| {
+        let name = format_ident!("{}", ty);
+        let methods =
+            available().filter_map(|function| function.to_method_tokens(ty, ext, &res.keysets));
+
+        quote! {
+            impl #name {
+                #(#methods)*
+            }
+        }
+    });
+
+    let error_kinds = res.error_types.to_tokens();
+    let ext_types = res.types.to_tokens();
 
     let out = quote! {
-        use crate::rpc::{RpcWriter, WriteError};
-        use crate::{args, Client, CallResponse, types::{UiOptions, Window, Tabpage, Buffer, Dictionary, LuaRef, Object}};
+        use crate::rpc::WriteError;
+        use crate::{args, Client, CallResponse, NvimErrorKind, types::{keysets, UiOptions, Window, Tabpage, Buffer, Dictionary, LuaRef, Object}};
+
+        /// The api level the bindings are generated for.
+        pub const MIN_API_LEVEL: i64 = #level;
+
+        #error_kinds
 
-        impl<W: RpcWriter> Client<W> {
+        #ext_types
+
+        impl Client {
             #(#functions)*
         }
+
+        #(#methods)*
     };
 
     println!("{}", out);
@@ -31,47 +60,77 @@ fn uievents(res: ApiMetadata) {
     let structs = res.ui_events.iter().filter_map(|event| event.to_struct());
     let members = res.ui_events.iter().map(|event| event.to_enum_arm());
     let display_members = res.ui_events.iter().map(|event| event.to_display_arm());
+    let into_owned_members = res.ui_events.iter().map(|event| event.to_into_owned_arm());
     let decode_matches = res.ui_events.iter().map(|event| event.to_decode_arm());
 
     let out = quote! {
         use std::fmt::Display;
+        use std::marker::PhantomData;
 
         use super::manual::*;
 
         #(#structs)*
 
         #[derive(Debug)]
-        pub enum UiEvent {
+        pub enum UiEvent<'a> {
             #(#members)*
+            /// Event unknown to us, e.g. from a newer version of neovim.
+            Unknown {
+                name: String,
+                params: Vec<rmpv::Value>,
+            },
         }
 
-        impl Display for UiEvent {
+        impl Display for UiEvent<'_> {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
                     #(#display_members)*
+                    Self::Unknown { name, .. } => write!(f, "{}", name),
+                }
+            }
+        }
+
+        impl UiEvent<'_> {
+            /// Converts the event into one that doesn't borrow from the
+            /// decoded message.
+            pub fn into_owned(self) -> UiEvent<'static> {
+                match self {
+                    #(#into_owned_members)*
+                    Self::Unknown { name, params } => UiEvent::Unknown { name, params },
                 }
             }
         }
 
-        impl<'de> serde::Deserialize<'de> for UiEvent {
+        impl<'de: 'a, 'a> serde::Deserialize<'de> for UiEvent<'a> {
             fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-                let redraw = rmpv::Value::deserialize(d)?;
-
-                let name = redraw[0].as_str();
-                // TODO(ville): Would be nice if this was possible to do with the derilization it self...
-                let params = redraw.as_array().and_then(|v| {
-                    if v[1].as_array().map(|v| v.is_empty()) == Some(true) {
-                        None
-                    } else {
-                        Some(v[1..].to_vec())
+                struct Visitor<'a>(PhantomData<UiEvent<'a>>);
+
+                impl<'de: 'a, 'a> serde::de::Visitor<'de> for Visitor<'a> {
+                    type Value = UiEvent<'a>;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        write!(f, "ui event")
+                    }
+
+                    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                        self,
+                        mut seq: A,
+                    ) -> Result<Self::Value, A::Error> {
+                        let name = seq
+                            .next_element::<CowStr>()?
+                            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+
+                        Ok(match name.0.as_ref() {
+                            #(#decode_matches)*
+                            name => UiEvent::Unknown {
+                                name: name.to_string(),
+                                params: event_params(&mut seq)?,
+                            },
+                        })
                     }
-                });
+                }
 
-                // TODO(ville): Error handling.
-                Ok(match (name, params) {
-                    #(#decode_matches)*
-                    v => panic!("failed to decode message {:?}", v),
-                })
+                d.deserialize_seq(Visitor(PhantomData))
             }
         }
     }
@@ -80,6 +139,24 @@ fn uievents(res: ApiMetadata) {
     println!("{}", out);
 }
 
+fn keysets(res: ApiMetadata) {
+    let structs = res
+        .keysets
+        .iter()
+        .map(|keyset| keyset.to_struct(&res.keysets));
+
+    let out = quote! {
+        use serde::Serialize;
+
+        #[allow(unused_imports)]
+        use super::{Buffer, Dictionary, LuaRef, Object, Tabpage, Window};
+
+        #(#structs)*
+    };
+
+    println!("{}", out);
+}
+
 fn main() {
     let args = std::env::args().collect::<Vec<String>>();
 
@@ -90,8 +167,15 @@ fn main() {
     let res = res.unwrap();
 
     match cmd.as_deref() {
-        Some("functions") => functions(res),
+        Some("functions") => {
+            let level = args
+                .get(2)
+                .and_then(|level| level.parse().ok())
+                .unwrap_or_else(|| usage_exit());
+            functions(res, level)
+        }
         Some("uievents") => uievents(res),
+        Some("keysets") => keysets(res),
         _ => usage_exit(),
     }
 
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
@@ -62,14 +64,19 @@ pub struct Function {
 }
 
 impl Function {
-    pub fn param_type_for(&self, param: &Parameter) -> TokenStream {
+    pub fn param_type_for(&self, param: &Parameter, keysets: &[KeySet]) -> TokenStream {
         match (self.name.as_ref(), param.name.as_ref()) {
             ("nvim_ui_attach", "options") => quote! { UiOptions },
-            _ => return self.param_type(&param.r#type),
+            _ => return self.param_type(&param.r#type, keysets),
         }
     }
 
-    fn param_type(&self, ty: &str) -> TokenStream {
+    fn param_type(&self, ty: &str, keysets: &[KeySet]) -> TokenStream {
+        if let Some(keyset) = KeySet::referenced_by(ty, keysets) {
+            let name = keyset.rust_name();
+            return quote! { &keysets::#name };
+        }
+
         match ty {
             "Boolean" => quote! { bool },
             "Integer" => quote! { i64 },
@@ -90,6 +97,8 @@ impl Function {
             "Dictionary" => quote! { &Dictionary },
             "Object" => quote! { &Object },
             "LuaRef" => quote! { &LuaRef },
+            // Keyset that isn't in the api-info.
+            s if s.starts_with("Dict(") => quote! { &Dictionary },
             s => unimplemented!("function param type '{}'", s),
         }
     }
@@ -115,16 +124,31 @@ impl Function {
             "Dictionary" => quote! { Dictionary },
             "Object" => quote! { Object },
             "LuaRef" => quote! { LuaRef },
+            // NOTE: The keyset structs are only for the params.
+            s if s.starts_with("Dict(") => quote! { Dictionary },
             s => unimplemented!("function output type '{}'", s),
         }
     }
 
-    fn args_in(&self) -> Vec<TokenStream> {
-        self.parameters
+    /// If the function is available at the target api level. Deprecated
+    /// functions are still available, they're only marked as deprecated.
+    pub fn available_at(&self, level: i64) -> bool {
+        self.since <= level
+    }
+
+    fn deprecated_attr(&self) -> Option<TokenStream> {
+        self.deprecated_since.map(|since| {
+            let note = format!("deprecated since api level {}", since);
+            quote! { #[deprecated(note = #note)] }
+        })
+    }
+
+    fn args_in(&self, params: &[Parameter], keysets: &[KeySet]) -> Vec<TokenStream> {
+        params
             .iter()
             .map(|p| {
                 let name = p.rust_name();
-                let ty = self.param_type_for(p);
+                let ty = self.param_type_for(p, keysets);
                 quote! {
                     #name: #ty
                 }
@@ -136,25 +160,159 @@ impl Function {
         self.parameters.iter().map(|p| p.rust_name()).collect()
     }
 
-    pub fn to_tokens(&self) -> Option<TokenStream> {
-        if self.deprecated_since.is_some() {
+    pub fn to_tokens(&self, keysets: &[KeySet]) -> TokenStream {
+        let fname: syn::Ident = syn::parse_str(&self.name).expect("failed to parse name");
+        let method = &self.name;
+        let args_in = self.args_in(&self.parameters, keysets);
+        let args_out = self.args_out();
+        let output = self.output_type_for(&self.return_type);
+        let deprecated = self.deprecated_attr();
+
+        quote! {
+            #deprecated
+            pub async fn #fname(&self, #(#args_in),*) -> Result<CallResponse<#output>, WriteError> {
+                self.call(#method, args![#(#args_out),*]).await
+            }
+        }
+    }
+
+    /// Generates a method for the handle type `ty` (e.g. `Buffer::get_lines`
+    /// for `nvim_buf_get_lines`), if the function is a method of it.
+    pub fn to_method_tokens(
+        &self,
+        ty: &str,
+        ext: &ExtType,
+        keysets: &[KeySet],
+    ) -> Option<TokenStream> {
+        let (this, params) = self.parameters.split_first()?;
+        if !self.method || this.r#type != ty {
             return None;
         }
 
-        let fname: syn::Ident = syn::parse_str(&self.name).expect("failed to parse name");
+        let fname: syn::Ident =
+            syn::parse_str(self.name.strip_prefix(&ext.prefix)?).expect("failed to parse name");
         let method = &self.name;
-        let args_in = self.args_in();
-        let args_out = self.args_out();
+        let args_in = self.args_in(params, keysets);
+        let args_out = params.iter().map(|p| p.rust_name());
         let output = self.output_type_for(&self.return_type);
+        let deprecated = self.deprecated_attr();
 
         Some(quote! {
-            pub async fn #fname(&mut self, #(#args_in),*) -> Result<CallResponse<#output>, WriteError> {
-                self.call(#method, args![#(#args_out),*]).await
+            #deprecated
+            pub async fn #fname(&self, client: &Client, #(#args_in),*) -> Result<CallResponse<#output>, WriteError> {
+                client.call(#method, args![self #(, #args_out)*]).await
             }
         })
     }
 }
 
+/// Set of keys accepted by an options dictionary (e.g. `opts` of
+/// `nvim_open_win`). Functions refer to the keysets with `Dict(name)` types.
+#[derive(Debug, serde::Deserialize)]
+pub struct KeySet {
+    pub name: String,
+    pub keys: Vec<String>,
+    /// Types of the keys. Older api-info doesn't have these, in which case
+    /// all the keys are `Object`s.
+    #[serde(default)]
+    pub types: HashMap<String, String>,
+}
+
+impl KeySet {
+    /// Finds the keyset referred by `ty`, if it is a `Dict(name)` type.
+    fn referenced_by<'a>(ty: &str, keysets: &'a [KeySet]) -> Option<&'a KeySet> {
+        let name = ty.strip_prefix("Dict(")?.strip_suffix(')')?;
+        keysets.iter().find(|keyset| keyset.name == name)
+    }
+
+    pub fn rust_name(&self) -> syn::Ident {
+        syn::parse_str(&self.name.as_pascal_case()).expect("failed to parse name")
+    }
+
+    fn field_name(key: &str) -> syn::Ident {
+        // NOTE: Keys like `type` need to be raw identifiers. Serde
+        // strips the `r#` prefix from the serialized name.
+        syn::parse_str(key).unwrap_or_else(|_| format_ident!("r#{}", key))
+    }
+
+    /// Returns the field's type, and the type and conversion of the value
+    /// taken by the field's builder method.
+    fn field_type(ty: &str, keysets: &[KeySet]) -> (TokenStream, TokenStream, TokenStream) {
+        let plain = |ty: TokenStream| (ty.clone(), ty, quote! { value });
+
+        if let Some(keyset) = Self::referenced_by(ty, keysets) {
+            let name = keyset.rust_name();
+            return plain(quote! { #name });
+        }
+
+        match ty {
+            "Boolean" => plain(quote! { bool }),
+            "Integer" => plain(quote! { i64 }),
+            "Float" => plain(quote! { f64 }),
+            "String" => (
+                quote! { String },
+                quote! { impl Into<String> },
+                quote! { value.into() },
+            ),
+            "Window" => plain(quote! { Window }),
+            "Tabpage" => plain(quote! { Tabpage }),
+            "Buffer" => plain(quote! { Buffer }),
+            "ArrayOf(String)" => plain(quote! { Vec<String> }),
+            "ArrayOf(Integer)" => plain(quote! { Vec<i64> }),
+            "Array" => plain(quote! { Vec<rmpv::Value> }),
+            "Dictionary" => plain(quote! { Dictionary }),
+            "LuaRef" => plain(quote! { LuaRef }),
+            // Object, and the types we don't have a better type for (e.g.
+            // unions).
+            _ => (
+                quote! { Object },
+                quote! { impl Into<rmpv::Value> },
+                quote! { Object::new(value) },
+            ),
+        }
+    }
+
+    pub fn to_struct(&self, keysets: &[KeySet]) -> TokenStream {
+        let name = self.rust_name();
+
+        let (fields, builders): (Vec<_>, Vec<_>) = self
+            .keys
+            .iter()
+            .map(|key| {
+                let field = Self::field_name(key);
+                let ty = self.types.get(key).map(String::as_str).unwrap_or("Object");
+                let (ty, arg, value) = Self::field_type(ty, keysets);
+
+                (
+                    // NOTE: The `Option` is spelled out, because there
+                    // is a keyset named `option`.
+                    quote! {
+                        #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+                        pub #field: ::std::option::Option<#ty>,
+                    },
+                    quote! {
+                        pub fn #field(mut self, value: #arg) -> Self {
+                            self.#field = Some(#value);
+                            self
+                        }
+                    },
+                )
+            })
+            .unzip();
+
+        quote! {
+            #[derive(Debug, Default, PartialEq, Serialize)]
+            pub struct #name {
+                #(#fields)*
+            }
+
+            impl #name {
+                #(#builders)*
+            }
+        }
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct ExtType {
     pub id: i64,
@@ -171,6 +329,38 @@ pub struct Types {
     pub tabpage: ExtType,
 }
 
+impl Types {
+    /// The handle types, by their api-info type names.
+    pub fn handles(&self) -> [(&'static str, &ExtType); 3] {
+        [
+            ("Buffer", &self.buffer),
+            ("Window", &self.window),
+            ("Tabpage", &self.tabpage),
+        ]
+    }
+
+    /// Generates the msgpack ext type ids for the handle types.
+    pub fn to_tokens(&self) -> TokenStream {
+        let buffer = self.buffer.id as i8;
+        let window = self.window.id as i8;
+        let tabpage = self.tabpage.id as i8;
+
+        quote! {
+            impl Buffer {
+                pub const EXT_ID: i8 = #buffer;
+            }
+
+            impl Window {
+                pub const EXT_ID: i8 = #window;
+            }
+
+            impl Tabpage {
+                pub const EXT_ID: i8 = #tabpage;
+            }
+        }
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct ExtErrorType {
     pub id: i64,
@@ -184,6 +374,34 @@ pub struct ErrorTypes {
     pub validation: ExtErrorType,
 }
 
+impl ErrorTypes {
+    /// Generates the mapping from the error type ids to `NvimErrorKind`.
+    pub fn to_tokens(&self) -> TokenStream {
+        let exception = self.exception.id;
+        let validation = self.validation.id;
+
+        quote! {
+            impl NvimErrorKind {
+                pub fn from_id(id: i64) -> Option<Self> {
+                    match id {
+                        #exception => Some(Self::Exception),
+                        #validation => Some(Self::Validation),
+                        _ => None,
+                    }
+                }
+
+                /// Id of the error type in the api metadata.
+                pub fn id(&self) -> i64 {
+                    match self {
+                        Self::Exception => #exception,
+                        Self::Validation => #validation,
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct UiEvent {
     pub parameters: Vec<Parameter>,
@@ -199,16 +417,15 @@ impl UiEvent {
 
         if self.parameters.is_empty() {
             quote! {
-                (Some(#name), None) => UiEvent::#member,
+                #name => {
+                    skip_remaining(&mut seq)?;
+                    UiEvent::#member
+                }
             }
         } else {
-            quote! (
-                (Some(#name), Some(params)) => UiEvent::#member({
-                    params.into_iter().map(#member::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-                }),
-            )
+            quote! {
+                #name => UiEvent::#member(event_params(&mut seq)?),
+            }
         }
     }
 
@@ -228,6 +445,25 @@ impl UiEvent {
         }
     }
 
+    pub fn to_into_owned_arm(&self) -> TokenStream {
+        let name: syn::Ident =
+            syn::parse_str(&self.name.as_pascal_case()).expect("failed to parse name");
+
+        if self.parameters.is_empty() {
+            quote! {
+                Self::#name => UiEvent::#name,
+            }
+        } else if self.borrows() {
+            quote! {
+                Self::#name(v) => UiEvent::#name(v.into_iter().map(#name::into_owned).collect()),
+            }
+        } else {
+            quote! {
+                Self::#name(v) => UiEvent::#name(v),
+            }
+        }
+    }
+
     pub fn to_enum_arm(&self) -> TokenStream {
         let name: syn::Ident =
             syn::parse_str(&self.name.as_pascal_case()).expect("failed to parse name");
@@ -236,6 +472,10 @@ impl UiEvent {
             quote! {
                 pub #name,
             }
+        } else if self.borrows() {
+            quote! {
+                pub #name(Vec<#name<'a>>),
+            }
         } else {
             quote! {
                 pub #name(Vec<#name>),
@@ -259,18 +499,37 @@ impl UiEvent {
                 pub #name: #ty,
             }
         });
+        let field_names = self
+            .parameters
+            .iter()
+            .map(|param| format_ident!("{}", &param.name));
+
+        let lifetime = self.borrows().then(|| quote! { <'a> });
 
         Some(quote! {
-            #[derive(Debug, serde::Deserialize)]
-            pub struct #name {
+            #[derive(Debug)]
+            pub struct #name #lifetime {
                 #(#fields)*
             }
+
+            impl_seq_deserialize!(#name #lifetime { #(#field_names),* });
         })
     }
 
+    /// If the event's param struct borrows from the decoded message.
+    fn borrows(&self) -> bool {
+        self.parameters
+            .iter()
+            .any(|param| self.field_borrows(&param.name))
+    }
+
+    fn field_borrows(&self, param: &str) -> bool {
+        matches!((self.name.as_ref(), param), ("grid_line", "data"))
+    }
+
     fn field_type_for(&self, param: &str, ty: &str) -> TokenStream {
         match (self.name.as_ref(), param) {
-            ("grid_line", "data") => quote! { Vec<GridLineData> },
+            ("grid_line", "data") => quote! { Vec<GridLineData<'a>> },
             ("hl_attr_define", "rgb_attrs") => quote! { HlAttr },
             ("hl_attr_define", "cterm_attrs") => quote! { HlAttr },
             ("mode_info_set", "cursor_styles") => quote! { Vec<ModeInfo> },
@@ -280,6 +539,11 @@ impl UiEvent {
             ("cmdline_show", "content") => quote! { Vec<CmdlineContent> },
             ("cmdline_block_show", "lines") => quote! { Vec<Vec<CmdlineContent>> },
             ("cmdline_block_append", "lines") => quote! { Vec<CmdlineContent> },
+            ("msg_show", "content") => quote! { Vec<MsgContent> },
+            ("msg_showcmd", "content") => quote! { Vec<MsgContent> },
+            ("msg_showmode", "content") => quote! { Vec<MsgContent> },
+            ("msg_ruler", "content") => quote! { Vec<MsgContent> },
+            ("msg_history_show", "entries") => quote! { Vec<MsgHistoryEntry> },
             _ => return self.field_type(ty),
         }
     }
@@ -313,6 +577,9 @@ impl UiEvent {
     pub fn has_manual_type(&self) -> bool {
         match self.name.as_ref() {
             "option_set" => true,
+            // NOTE: Manually implemented to have scroll_delta
+            // optional, so older versions of neovim are supported.
+            "win_viewport" => true,
             _ => false,
         }
     }
@@ -339,4 +606,8 @@ pub struct ApiMetadata {
 
     pub types: Types,
     pub error_types: ErrorTypes,
+
+    /// Not present in older api-info.
+    #[serde(default)]
+    pub keysets: Vec<KeySet>,
 }
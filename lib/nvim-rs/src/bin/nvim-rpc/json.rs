@@ -0,0 +1,68 @@
+//! Conversions between JSON and msgpack values.
+
+use serde_json::{Map, Number, Value};
+
+/// Parses a command line argument. Arguments that aren't valid JSON are
+/// taken as strings, so that e.g. `nvim-rpc call nvim_command 'echo 1'`
+/// works without extra quoting.
+pub fn parse_arg(arg: &str) -> rmpv::Value {
+    match serde_json::from_str(arg) {
+        Ok(value) => to_msgpack(value),
+        Err(_) => rmpv::Value::from(arg),
+    }
+}
+
+pub fn to_msgpack(value: Value) -> rmpv::Value {
+    match value {
+        Value::Null => rmpv::Value::Nil,
+        Value::Bool(v) => rmpv::Value::from(v),
+        Value::Number(v) => match (v.as_i64(), v.as_u64()) {
+            (Some(v), _) => rmpv::Value::from(v),
+            (None, Some(v)) => rmpv::Value::from(v),
+            _ => rmpv::Value::from(v.as_f64().unwrap_or_default()),
+        },
+        Value::String(v) => rmpv::Value::from(v),
+        Value::Array(v) => rmpv::Value::Array(v.into_iter().map(to_msgpack).collect()),
+        Value::Object(v) => rmpv::Value::Map(
+            v.into_iter()
+                .map(|(k, v)| (rmpv::Value::from(k), to_msgpack(v)))
+                .collect(),
+        ),
+    }
+}
+
+pub fn from_msgpack(value: rmpv::Value) -> Value {
+    match value {
+        rmpv::Value::Nil => Value::Null,
+        rmpv::Value::Boolean(v) => Value::Bool(v),
+        rmpv::Value::Integer(v) => match (v.as_i64(), v.as_u64()) {
+            (Some(v), _) => Value::from(v),
+            (None, Some(v)) => Value::from(v),
+            _ => Value::Null,
+        },
+        rmpv::Value::F32(v) => Number::from_f64(v.into()).map_or(Value::Null, Value::Number),
+        rmpv::Value::F64(v) => Number::from_f64(v).map_or(Value::Null, Value::Number),
+        rmpv::Value::String(v) => Value::String(match v.as_str() {
+            Some(s) => s.to_string(),
+            None => String::from_utf8_lossy(v.as_bytes()).into_owned(),
+        }),
+        rmpv::Value::Binary(v) => Value::String(String::from_utf8_lossy(&v).into_owned()),
+        rmpv::Value::Array(v) => Value::Array(v.into_iter().map(from_msgpack).collect()),
+        rmpv::Value::Map(v) => Value::Object(
+            v.into_iter()
+                .map(|(k, v)| {
+                    let k = match k {
+                        rmpv::Value::String(k) => k.into_str().unwrap_or_default(),
+                        k => k.to_string(),
+                    };
+                    (k, from_msgpack(v))
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        // Buffer, window and tabpage handles. The handle is an integer, which
+        // neovim also accepts in place of the handle.
+        rmpv::Value::Ext(_, data) => rmp_serde::from_slice::<i64>(&data)
+            .map(Value::from)
+            .unwrap_or(Value::Null),
+    }
+}
@@ -0,0 +1,193 @@
+//! Command line client for a running neovim. For example, to edit commit
+//! messages in the neovim running the terminal:
+//!
+//! ```sh
+//! GIT_EDITOR="nvim-rpc open --wait" git commit
+//! ```
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use futures::channel::mpsc;
+use futures::{FutureExt, StreamExt};
+
+use nvim_rs::types::{keysets, ApiInfo, Object};
+use nvim_rs::{args, transport, CallError, Client};
+
+mod json;
+
+/// Notification we ask neovim to send when a buffer we wait for is deleted.
+const BUF_DELETED: &str = "nvim_rpc_buf_deleted";
+
+#[derive(Parser)]
+#[clap(name = "nvim-rpc", version, about = "Control a running neovim")]
+struct Args {
+    /// Address of the neovim server, either a socket path or host:port.
+    /// Defaults to $NVIM, or $NVIM_LISTEN_ADDRESS for older neovim.
+    #[clap(long, short, value_name = "ADDRESS", env = "NVIM")]
+    server: Option<String>,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Call an api method, and print the result as JSON.
+    Call {
+        method: String,
+        /// Arguments of the call as JSON. Arguments that are not valid JSON
+        /// are passed as strings.
+        #[clap(allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Send keys to neovim, like `nvim_input`.
+    Send { keys: String },
+    /// Open files for editing.
+    Open {
+        /// Wait until the opened buffers are deleted (e.g. with `:bdelete`).
+        #[clap(long)]
+        wait: bool,
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+fn error_exit(err: impl Display) -> ! {
+    eprintln!("nvim-rpc: {}", err);
+    std::process::exit(1);
+}
+
+/// Waits for the call's result. Calls to functions that don't return
+/// anything get nil as their result.
+async fn result(res: nvim_rs::CallResponse<rmpv::Value>) -> Result<rmpv::Value, Box<dyn Error>> {
+    match res.await {
+        Ok(value) => Ok(value),
+        Err(CallError::MissingResult) => Ok(rmpv::Value::Nil),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn open(
+    client: &Client,
+    files: Vec<PathBuf>,
+    wait: bool,
+    mut deleted: mpsc::UnboundedReceiver<rmpv::Value>,
+) -> Result<(), Box<dyn Error>> {
+    // NOTE: Neovim's cwd is most likely different from ours.
+    let cwd = std::env::current_dir()?;
+
+    let channel = if wait {
+        let info = client
+            .call::<ApiInfo, _, _>("nvim_get_api_info", args![])
+            .await?
+            .await?;
+        Some(info.channel)
+    } else {
+        None
+    };
+
+    let mut waiting = HashSet::new();
+    for file in files {
+        let path = cwd.join(file);
+        let cmd = keysets::Cmd::default()
+            .cmd("edit")
+            .args(vec![path.to_string_lossy().as_ref().into()]);
+        client
+            .nvim_cmd(&cmd, &keysets::CmdOpts::default())
+            .await?
+            .await?;
+
+        if let Some(channel) = channel {
+            let buf = client.nvim_get_current_buf().await?.await?;
+            let opts = keysets::CreateAutocmd::default()
+                .buffer(buf)
+                .once(true)
+                .command(format!(
+                    "call rpcnotify({}, '{}', {})",
+                    channel,
+                    BUF_DELETED,
+                    buf.handle()
+                ));
+            let events = Object::new(vec!["BufDelete".into(), rmpv::Value::from("BufWipeout")]);
+            client.nvim_create_autocmd(&events, &opts).await?.await?;
+
+            waiting.insert(buf.handle());
+        }
+    }
+
+    while !waiting.is_empty() {
+        match deleted.next().await {
+            Some(params) => {
+                if let Some(buf) = params[0].as_i64() {
+                    waiting.remove(&buf);
+                }
+            }
+            None => break,
+        }
+    }
+
+    Ok(())
+}
+
+async fn run(
+    client: Client,
+    command: Command,
+    deleted: mpsc::UnboundedReceiver<rmpv::Value>,
+) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Call { method, args } => {
+            let args = args
+                .iter()
+                .map(|arg| json::parse_arg(arg))
+                .collect::<Vec<_>>();
+            let res = result(client.call(&method, args).await?).await?;
+            println!("{}", json::from_msgpack(res));
+        }
+        Command::Send { keys } => {
+            client.nvim_input(&keys).await?.await?;
+        }
+        Command::Open { files, wait } => open(&client, files, wait, deleted).await?,
+    }
+
+    Ok(())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let args = Args::parse();
+
+    let address = args
+        .server
+        .or_else(|| std::env::var("NVIM_LISTEN_ADDRESS").ok())
+        .unwrap_or_else(|| error_exit("no server address, use --server or set $NVIM"));
+
+    let (client, mut session) = transport::tokio::connect(&address)
+        .await
+        .unwrap_or_else(|err| error_exit(format!("failed to connect to {}: {}", address, err)));
+
+    let deleted = session.notifications(BUF_DELETED);
+    let wait = matches!(args.command, Command::Open { wait: true, .. });
+
+    let run = run(client, args.command, deleted);
+    tokio::pin!(run);
+
+    let res = tokio::select! {
+        res = &mut run => res,
+        _ = session.run() => match run.now_or_never() {
+            // NOTE: The response might've been the last thing read
+            // before the connection closed (e.g. for `call nvim_command qa`).
+            Some(res) => res,
+            // NOTE: When waiting for buffers, nvim exiting is fine.
+            None if wait => Ok(()),
+            None => Err("connection to nvim closed".into()),
+        },
+    };
+
+    if let Err(err) = res {
+        error_exit(err);
+    }
+}
@@ -1,16 +1,23 @@
 use std::{
     any::{Any, TypeId},
+    collections::HashMap,
+    fmt::Display,
     pin::Pin,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, MutexGuard, Weak,
+    },
 };
 
-use futures::channel::oneshot;
+use futures::channel::{mpsc, oneshot};
 use futures::prelude::*;
 
 use serde::Deserialize;
 
 use crate::rpc::{
     message::Response,
-    writer::{RpcWriter, WriteError},
+    record::{Direction, Recorder},
+    writer::{encode_rpc, WriteError},
 };
 
 #[macro_export]
@@ -30,13 +37,102 @@ macro_rules! dict {
     }};
 }
 
+/// Handle to the neovim rpc connection. The handle is cheap to clone, and
+/// all the clones share the same connection.
+///
+/// Outgoing messages are encoded by the caller and handed over to the writer
+/// task (see `Client::new`), so callers never block each other.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: Arc<Inner>,
+}
+
 #[derive(Debug)]
-pub struct Client<W: RpcWriter> {
-    writer: W,
-    msgid_counter: u32,
-    callbacks: Vec<(u32, oneshot::Sender<Response>)>,
+struct Inner {
+    msgid_counter: AtomicU32,
+    callbacks: Mutex<HashMap<u32, oneshot::Sender<Response>>>,
+    sender: mpsc::UnboundedSender<Vec<u8>>,
+    recorder: Mutex<Option<Recorder>>,
+}
+
+impl Inner {
+    fn callbacks(&self) -> MutexGuard<'_, HashMap<u32, oneshot::Sender<Response>>> {
+        // NOTE: The lock is never held while calling into user code, so
+        // it can't be poisoned.
+        self.callbacks.lock().expect("callbacks lock poisoned")
+    }
+}
+
+/// Removes the callback of a call when the call's response future is dropped,
+/// so dropped callers won't leave their callbacks behind.
+struct CallbackGuard {
+    msgid: u32,
+    inner: Weak<Inner>,
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.callbacks().remove(&self.msgid);
+        }
+    }
+}
+
+/// The error types neovim reports in its error responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NvimErrorKind {
+    Exception,
+    Validation,
+}
+
+impl Display for NvimErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exception => write!(f, "exception"),
+            Self::Validation => write!(f, "validation"),
+        }
+    }
+}
+
+/// Decoded error response from neovim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NvimError {
+    pub kind: NvimErrorKind,
+    pub message: String,
+}
+
+impl NvimError {
+    /// Decode the error value of a response. Neovim sends its errors as
+    /// `[type, message]` arrays, where the type is one of the ids from the
+    /// api-info's `error_types`.
+    pub fn from_value(value: &rmpv::Value) -> Option<Self> {
+        match value {
+            rmpv::Value::Array(arr) => match arr.as_slice() {
+                [kind, message] => Some(Self {
+                    kind: NvimErrorKind::from_id(kind.as_i64()?)?,
+                    message: message.as_str()?.to_string(),
+                }),
+                _ => None,
+            },
+            // NOTE: Other rpc clients (e.g. remote plugins) might
+            // send plain strings as errors.
+            rmpv::Value::String(message) => Some(Self {
+                kind: NvimErrorKind::Exception,
+                message: message.as_str()?.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Display for NvimError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} error: {}", self.kind, self.message)
+    }
 }
 
+impl std::error::Error for NvimError {}
+
 #[derive(Debug, PartialEq)]
 pub enum CallError {
     /// The operation was cancelled, because other end (of the internal channel)
@@ -45,11 +141,38 @@ pub enum CallError {
     /// The result field is missing.
     MissingResult,
     /// The call resulted into a error response (e.g. bad API call).
-    Error(rmpv::Value),
+    Error(NvimError),
     /// Decoding the result failed.
     DecodeResult,
-    // Decoding the error failed.
-    //DecodeError,
+    /// The call resulted into a error response, but decoding the error
+    /// failed.
+    DecodeError(rmpv::Value),
+    /// A call in a batch failed. The index is the position of the failed
+    /// call in the batch.
+    Batch { index: usize, error: Box<CallError> },
+}
+
+impl Display for CallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "call cancelled"),
+            Self::MissingResult => write!(f, "missing result"),
+            Self::Error(err) => write!(f, "{}", err),
+            Self::DecodeResult => write!(f, "failed to decode result"),
+            Self::DecodeError(value) => write!(f, "failed to decode error: {}", value),
+            Self::Batch { index, error } => write!(f, "call {} of batch failed: {}", index, error),
+        }
+    }
+}
+
+impl std::error::Error for CallError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Error(err) => Some(err),
+            Self::Batch { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl From<oneshot::Canceled> for CallError {
@@ -60,68 +183,194 @@ impl From<oneshot::Canceled> for CallError {
 
 pub type CallResponse<T> = Pin<Box<dyn Future<Output = Result<T, CallError>> + Send>>;
 
-impl<W: RpcWriter> Client<W> {
-    pub fn new(writer: W) -> Self {
-        Self {
-            writer,
-            callbacks: Vec::new(),
-            msgid_counter: 0,
+impl Client {
+    /// Creates a new client. The returned future is the writer task, which
+    /// writes the outgoing messages to `writer`, and it needs to be polled
+    /// (e.g. spawned to an executor) for the client to work. The task
+    /// completes once all the clones of the client are dropped, or writing
+    /// to the writer fails.
+    pub fn new<W>(writer: W) -> (Self, impl Future<Output = Result<(), WriteError>>)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let (sender, receiver) = mpsc::unbounded();
+        let inner = Arc::new(Inner {
+            msgid_counter: AtomicU32::new(0),
+            callbacks: Mutex::new(HashMap::new()),
+            sender,
+            recorder: Mutex::new(None),
+        });
+
+        let task = write_loop(writer, receiver, Arc::downgrade(&inner));
+
+        (Self { inner }, task)
+    }
+
+    /// Record all the outgoing messages to `recorder`.
+    pub fn record(&self, recorder: Recorder) {
+        *self.inner.recorder.lock().expect("recorder lock poisoned") = Some(recorder);
+    }
+
+    fn send(&self, buf: Vec<u8>) -> Result<(), WriteError> {
+        if let Some(recorder) = self
+            .inner
+            .recorder
+            .lock()
+            .expect("recorder lock poisoned")
+            .as_ref()
+        {
+            recorder.record(Direction::Outgoing, &buf);
         }
+
+        self.inner
+            .sender
+            .unbounded_send(buf)
+            .map_err(|_| WriteError::Closed)
     }
 
-    pub async fn call<T, S, V>(&mut self, method: S, args: V) -> Result<CallResponse<T>, WriteError>
+    /// Call `method` on neovim. The request is written once this function
+    /// returns, and the response can be awaited through the returned future.
+    /// Calls are pipelined: multiple calls can be made before awaiting any of
+    /// the responses. See also `Client::batch`.
+    pub async fn call<T, S, V>(&self, method: S, args: V) -> Result<CallResponse<T>, WriteError>
     where
         T: for<'de> Deserialize<'de> + Any,
         S: AsRef<str>,
         V: serde::Serialize,
     {
-        let msgid = self.msgid_counter;
-        self.msgid_counter += 1;
+        let res = self.request(self.encode_request(method.as_ref(), &args)?)?;
+
+        Ok(async move {
+            let res = res.await?;
+
+            // The type `()` is special in a sense that it signals voidness of the
+            // returned result. But since the returned result doesn't necessarily exist,
+            // we'll need to handle it our selves.
+            let res = if res.is_none() && TypeId::of::<T>() == TypeId::of::<()>() {
+                rmpv::Value::Nil
+            } else {
+                res.ok_or(CallError::MissingResult)?
+            };
+
+            rmpv::ext::from_value::<T>(res).map_err(|_err| CallError::DecodeResult)
+        }
+        .boxed())
+    }
+
+    /// Encodes a request, returning its msgid and the encoded message.
+    pub(crate) fn encode_request<V>(
+        &self,
+        method: &str,
+        args: &V,
+    ) -> Result<(u32, Vec<u8>), WriteError>
+    where
+        V: serde::Serialize,
+    {
+        let msgid = self.inner.msgid_counter.fetch_add(1, Ordering::Relaxed);
+        let buf = encode_rpc(&(0, msgid, method, args))?;
+        Ok((msgid, buf))
+    }
 
+    /// Writes an encoded request (see `Client::encode_request`), and returns
+    /// a future resolving to the response's result.
+    pub(crate) fn request(
+        &self,
+        (msgid, buf): (u32, Vec<u8>),
+    ) -> Result<impl Future<Output = Result<Option<rmpv::Value>, CallError>>, WriteError> {
         let (sender, receiver) = oneshot::channel();
-        self.callbacks.push((msgid, sender));
-
-        self.writer
-            .write_rpc_request(msgid, method.as_ref(), &args)
-            .await?;
-
-        Ok(receiver
-            .map(|res| {
-                let res = res?;
-
-                if let Some(error) = res.error {
-                    // TODO(ville): Decode the error value.
-                    return Err(CallError::Error(error));
-                }
-
-                // The type `()` is special in a sense that it signals voidness of the
-                // returned result. But since the returned result doesn't necessarily exist,
-                // we'll need to handle it our selves.
-                let res = if res.result.is_none() && TypeId::of::<T>() == TypeId::of::<()>() {
-                    rmpv::Value::Nil
-                } else {
-                    res.result.ok_or(CallError::MissingResult)?
-                };
-
-                rmpv::ext::from_value::<T>(res).map_err(|_err| CallError::DecodeResult)
-            })
-            .boxed())
-    }
-
-    pub fn handle_response(&mut self, response: Response) -> Result<(), HandleError> {
-        let caller = self
-            .callbacks
-            .iter()
-            .position(|(msgid, _)| *msgid == response.msgid)
-            .map(|index| self.callbacks.swap_remove(index));
+        self.inner.callbacks().insert(msgid, sender);
+        let guard = CallbackGuard {
+            msgid,
+            inner: Arc::downgrade(&self.inner),
+        };
+
+        self.send(buf)?;
+
+        Ok(async move {
+            let res = receiver.await;
+            drop(guard);
+            let res = res?;
+
+            if let Some(error) = res.error {
+                return Err(match NvimError::from_value(&error) {
+                    Some(error) => CallError::Error(error),
+                    None => CallError::DecodeError(error),
+                });
+            }
+
+            Ok(res.result)
+        })
+    }
+
+    /// Send a notification to neovim.
+    pub async fn notify<S, V>(&self, method: S, args: V) -> Result<(), WriteError>
+    where
+        S: AsRef<str>,
+        V: serde::Serialize,
+    {
+        self.send(encode_rpc(&(2, method.as_ref(), &args))?)
+    }
+
+    /// Write a response for a request received from neovim (see
+    /// `RequestHandlers::handle`).
+    pub async fn respond(
+        &self,
+        msgid: u32,
+        result: Result<rmpv::Value, rmpv::Value>,
+    ) -> Result<(), WriteError> {
+        let buf = match result {
+            Ok(result) => encode_rpc(&(1, msgid, None::<&rmpv::Value>, Some(&result))),
+            Err(error) => encode_rpc(&(1, msgid, Some(&error), None::<&rmpv::Value>)),
+        }?;
+
+        self.send(buf)
+    }
+
+    pub fn handle_response(&self, response: Response) -> Result<(), HandleError> {
+        let caller = self.inner.callbacks().remove(&response.msgid);
 
         match caller {
-            Some((_, recv)) => recv.send(response).map_err(HandleError::CallerDropped),
+            Some(recv) => recv.send(response).map_err(HandleError::CallerDropped),
             None => Err(HandleError::CallerMissing(response)),
         }
     }
 }
 
+async fn write_loop<W>(
+    mut writer: W,
+    mut receiver: mpsc::UnboundedReceiver<Vec<u8>>,
+    inner: Weak<Inner>,
+) -> Result<(), WriteError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let res = async {
+        while let Some(buf) = receiver.next().await {
+            writer.write_all(&buf).await?;
+
+            // Write everything that is already queued before flushing.
+            while let Some(Some(buf)) = receiver.next().now_or_never() {
+                writer.write_all(&buf).await?;
+            }
+
+            writer.flush().await?;
+        }
+
+        Ok(())
+    }
+    .await;
+
+    if res.is_err() {
+        // Nothing will be written anymore, so the pending calls won't ever
+        // get their responses. Drop the callbacks to cancel the calls.
+        if let Some(inner) = inner.upgrade() {
+            inner.callbacks().clear();
+        }
+    }
+
+    res
+}
+
 #[derive(Debug)]
 pub enum HandleError {
     /// The message was a response, but the caller wasn't found based on the
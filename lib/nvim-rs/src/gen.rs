@@ -1,66 +1,90 @@
-use crate::rpc::{RpcWriter, WriteError};
+use crate::rpc::WriteError;
 use crate::{
     args,
-    types::{Buffer, Dictionary, LuaRef, Object, Tabpage, UiOptions, Window},
-    CallResponse, Client,
+    types::{keysets, Buffer, Dictionary, LuaRef, Object, Tabpage, UiOptions, Window},
+    CallResponse, Client, NvimErrorKind,
 };
-impl<W: RpcWriter> Client<W> {
+#[doc = r" The api level the bindings are generated for."]
+pub const MIN_API_LEVEL: i64 = 10i64;
+impl NvimErrorKind {
+    pub fn from_id(id: i64) -> Option<Self> {
+        match id {
+            0i64 => Some(Self::Exception),
+            1i64 => Some(Self::Validation),
+            _ => None,
+        }
+    }
+    #[doc = r" Id of the error type in the api metadata."]
+    pub fn id(&self) -> i64 {
+        match self {
+            Self::Exception => 0i64,
+            Self::Validation => 1i64,
+        }
+    }
+}
+impl Buffer {
+    pub const EXT_ID: i8 = 0i8;
+}
+impl Window {
+    pub const EXT_ID: i8 = 1i8;
+}
+impl Tabpage {
+    pub const EXT_ID: i8 = 2i8;
+}
+impl Client {
     pub async fn nvim_get_autocmds(
-        &mut self,
-        opts: &Dictionary,
+        &self,
+        opts: &keysets::GetAutocmds,
     ) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
         self.call("nvim_get_autocmds", args![opts]).await
     }
     pub async fn nvim_create_autocmd(
-        &mut self,
+        &self,
         event: &Object,
-        opts: &Dictionary,
+        opts: &keysets::CreateAutocmd,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_create_autocmd", args![event, opts]).await
     }
-    pub async fn nvim_del_autocmd(&mut self, id: i64) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_del_autocmd(&self, id: i64) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_del_autocmd", args![id]).await
     }
     pub async fn nvim_clear_autocmds(
-        &mut self,
-        opts: &Dictionary,
+        &self,
+        opts: &keysets::ClearAutocmds,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_clear_autocmds", args![opts]).await
     }
     pub async fn nvim_create_augroup(
-        &mut self,
+        &self,
         name: &str,
-        opts: &Dictionary,
+        opts: &keysets::CreateAugroup,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_create_augroup", args![name, opts]).await
     }
-    pub async fn nvim_del_augroup_by_id(
-        &mut self,
-        id: i64,
-    ) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_del_augroup_by_id(&self, id: i64) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_del_augroup_by_id", args![id]).await
     }
     pub async fn nvim_del_augroup_by_name(
-        &mut self,
+        &self,
         name: &str,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_del_augroup_by_name", args![name]).await
     }
     pub async fn nvim_exec_autocmds(
-        &mut self,
+        &self,
         event: &Object,
-        opts: &Dictionary,
+        opts: &keysets::ExecAutocmds,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_exec_autocmds", args![event, opts]).await
     }
     pub async fn nvim_buf_line_count(
-        &mut self,
+        &self,
         buffer: &Buffer,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_buf_line_count", args![buffer]).await
     }
     pub async fn nvim_buf_attach(
-        &mut self,
+        &self,
         buffer: &Buffer,
         send_buffer: bool,
         opts: &Dictionary,
@@ -68,14 +92,11 @@ impl<W: RpcWriter> Client<W> {
         self.call("nvim_buf_attach", args![buffer, send_buffer, opts])
             .await
     }
-    pub async fn nvim_buf_detach(
-        &mut self,
-        buffer: &Buffer,
-    ) -> Result<CallResponse<bool>, WriteError> {
+    pub async fn nvim_buf_detach(&self, buffer: &Buffer) -> Result<CallResponse<bool>, WriteError> {
         self.call("nvim_buf_detach", args![buffer]).await
     }
     pub async fn nvim_buf_get_lines(
-        &mut self,
+        &self,
         buffer: &Buffer,
         start: i64,
         end: i64,
@@ -88,7 +109,7 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_buf_set_lines(
-        &mut self,
+        &self,
         buffer: &Buffer,
         start: i64,
         end: i64,
@@ -102,7 +123,7 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_buf_set_text(
-        &mut self,
+        &self,
         buffer: &Buffer,
         start_row: i64,
         start_col: i64,
@@ -117,7 +138,7 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_buf_get_text(
-        &mut self,
+        &self,
         buffer: &Buffer,
         start_row: i64,
         start_col: i64,
@@ -132,45 +153,45 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_buf_get_offset(
-        &mut self,
+        &self,
         buffer: &Buffer,
         index: i64,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_buf_get_offset", args![buffer, index]).await
     }
     pub async fn nvim_buf_get_var(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_buf_get_var", args![buffer, name]).await
     }
     pub async fn nvim_buf_get_changedtick(
-        &mut self,
+        &self,
         buffer: &Buffer,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_buf_get_changedtick", args![buffer]).await
     }
     pub async fn nvim_buf_get_keymap(
-        &mut self,
+        &self,
         buffer: &Buffer,
         mode: &str,
     ) -> Result<CallResponse<Vec<Dictionary>>, WriteError> {
         self.call("nvim_buf_get_keymap", args![buffer, mode]).await
     }
     pub async fn nvim_buf_set_keymap(
-        &mut self,
+        &self,
         buffer: &Buffer,
         mode: &str,
         lhs: &str,
         rhs: &str,
-        opts: &Dictionary,
+        opts: &keysets::Keymap,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_buf_set_keymap", args![buffer, mode, lhs, rhs, opts])
             .await
     }
     pub async fn nvim_buf_del_keymap(
-        &mut self,
+        &self,
         buffer: &Buffer,
         mode: &str,
         lhs: &str,
@@ -179,7 +200,7 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_buf_set_var(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
         value: &Object,
@@ -188,53 +209,60 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_buf_del_var(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_buf_del_var", args![buffer, name]).await
     }
     pub async fn nvim_buf_get_name(
-        &mut self,
+        &self,
         buffer: &Buffer,
     ) -> Result<CallResponse<String>, WriteError> {
         self.call("nvim_buf_get_name", args![buffer]).await
     }
+    #[deprecated(note = "deprecated since api level 2")]
+    pub async fn nvim_buf_get_number(
+        &self,
+        buffer: &Buffer,
+    ) -> Result<CallResponse<i64>, WriteError> {
+        self.call("nvim_buf_get_number", args![buffer]).await
+    }
     pub async fn nvim_buf_set_name(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_buf_set_name", args![buffer, name]).await
     }
     pub async fn nvim_buf_is_loaded(
-        &mut self,
+        &self,
         buffer: &Buffer,
     ) -> Result<CallResponse<bool>, WriteError> {
         self.call("nvim_buf_is_loaded", args![buffer]).await
     }
     pub async fn nvim_buf_delete(
-        &mut self,
+        &self,
         buffer: &Buffer,
         opts: &Dictionary,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_buf_delete", args![buffer, opts]).await
     }
     pub async fn nvim_buf_is_valid(
-        &mut self,
+        &self,
         buffer: &Buffer,
     ) -> Result<CallResponse<bool>, WriteError> {
         self.call("nvim_buf_is_valid", args![buffer]).await
     }
     pub async fn nvim_buf_del_mark(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
     ) -> Result<CallResponse<bool>, WriteError> {
         self.call("nvim_buf_del_mark", args![buffer, name]).await
     }
     pub async fn nvim_buf_set_mark(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
         line: i64,
@@ -245,54 +273,51 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_buf_get_mark(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
     ) -> Result<CallResponse<(i64, i64)>, WriteError> {
         self.call("nvim_buf_get_mark", args![buffer, name]).await
     }
     pub async fn nvim_buf_call(
-        &mut self,
+        &self,
         buffer: &Buffer,
         fun: &LuaRef,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_buf_call", args![buffer, fun]).await
     }
     pub async fn nvim_parse_cmd(
-        &mut self,
+        &self,
         str: &str,
         opts: &Dictionary,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_parse_cmd", args![str, opts]).await
     }
     pub async fn nvim_cmd(
-        &mut self,
-        cmd: &Dictionary,
-        opts: &Dictionary,
+        &self,
+        cmd: &keysets::Cmd,
+        opts: &keysets::CmdOpts,
     ) -> Result<CallResponse<String>, WriteError> {
         self.call("nvim_cmd", args![cmd, opts]).await
     }
     pub async fn nvim_create_user_command(
-        &mut self,
+        &self,
         name: &str,
         command: &Object,
-        opts: &Dictionary,
+        opts: &keysets::UserCommand,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_create_user_command", args![name, command, opts])
             .await
     }
-    pub async fn nvim_del_user_command(
-        &mut self,
-        name: &str,
-    ) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_del_user_command(&self, name: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_del_user_command", args![name]).await
     }
     pub async fn nvim_buf_create_user_command(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
         command: &Object,
-        opts: &Dictionary,
+        opts: &keysets::UserCommand,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call(
             "nvim_buf_create_user_command",
@@ -301,7 +326,7 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_buf_del_user_command(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
     ) -> Result<CallResponse<()>, WriteError> {
@@ -309,30 +334,27 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_get_commands(
-        &mut self,
-        opts: &Dictionary,
+        &self,
+        opts: &keysets::GetCommands,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_commands", args![opts]).await
     }
     pub async fn nvim_buf_get_commands(
-        &mut self,
+        &self,
         buffer: &Buffer,
-        opts: &Dictionary,
+        opts: &keysets::GetCommands,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_buf_get_commands", args![buffer, opts])
             .await
     }
-    pub async fn nvim_create_namespace(
-        &mut self,
-        name: &str,
-    ) -> Result<CallResponse<i64>, WriteError> {
+    pub async fn nvim_create_namespace(&self, name: &str) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_create_namespace", args![name]).await
     }
-    pub async fn nvim_get_namespaces(&mut self) -> Result<CallResponse<Dictionary>, WriteError> {
+    pub async fn nvim_get_namespaces(&self) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_namespaces", args![]).await
     }
     pub async fn nvim_buf_get_extmark_by_id(
-        &mut self,
+        &self,
         buffer: &Buffer,
         ns_id: i64,
         id: i64,
@@ -342,7 +364,7 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_buf_get_extmarks(
-        &mut self,
+        &self,
         buffer: &Buffer,
         ns_id: i64,
         start: &Object,
@@ -356,12 +378,12 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_buf_set_extmark(
-        &mut self,
+        &self,
         buffer: &Buffer,
         ns_id: i64,
         line: i64,
         col: i64,
-        opts: &Dictionary,
+        opts: &keysets::SetExtmark,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call(
             "nvim_buf_set_extmark",
@@ -369,8 +391,23 @@ impl<W: RpcWriter> Client<W> {
         )
         .await
     }
+    #[deprecated(note = "deprecated since api level 8")]
+    pub async fn nvim_buf_set_virtual_text(
+        &self,
+        buffer: &Buffer,
+        src_id: i64,
+        line: i64,
+        chunks: Vec<rmpv::Value>,
+        opts: &Dictionary,
+    ) -> Result<CallResponse<i64>, WriteError> {
+        self.call(
+            "nvim_buf_set_virtual_text",
+            args![buffer, src_id, line, chunks, opts],
+        )
+        .await
+    }
     pub async fn nvim_buf_del_extmark(
-        &mut self,
+        &self,
         buffer: &Buffer,
         ns_id: i64,
         id: i64,
@@ -379,7 +416,7 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_buf_add_highlight(
-        &mut self,
+        &self,
         buffer: &Buffer,
         ns_id: i64,
         hl_group: &str,
@@ -394,7 +431,7 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_buf_clear_namespace(
-        &mut self,
+        &self,
         buffer: &Buffer,
         ns_id: i64,
         line_start: i64,
@@ -406,63 +443,72 @@ impl<W: RpcWriter> Client<W> {
         )
         .await
     }
+    #[deprecated(note = "deprecated since api level 7")]
+    pub async fn nvim_buf_clear_highlight(
+        &self,
+        buffer: &Buffer,
+        ns_id: i64,
+        line_start: i64,
+        line_end: i64,
+    ) -> Result<CallResponse<()>, WriteError> {
+        self.call(
+            "nvim_buf_clear_highlight",
+            args![buffer, ns_id, line_start, line_end],
+        )
+        .await
+    }
     pub async fn nvim_set_decoration_provider(
-        &mut self,
+        &self,
         ns_id: i64,
-        opts: &Dictionary,
+        opts: &keysets::SetDecorationProvider,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_decoration_provider", args![ns_id, opts])
             .await
     }
     pub async fn nvim_get_option_value(
-        &mut self,
+        &self,
         name: &str,
-        opts: &Dictionary,
+        opts: &keysets::Option,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_get_option_value", args![name, opts]).await
     }
     pub async fn nvim_set_option_value(
-        &mut self,
+        &self,
         name: &str,
         value: &Object,
-        opts: &Dictionary,
+        opts: &keysets::Option,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_option_value", args![name, value, opts])
             .await
     }
-    pub async fn nvim_get_all_options_info(
-        &mut self,
-    ) -> Result<CallResponse<Dictionary>, WriteError> {
+    pub async fn nvim_get_all_options_info(&self) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_all_options_info", args![]).await
     }
     pub async fn nvim_get_option_info(
-        &mut self,
+        &self,
         name: &str,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_option_info", args![name]).await
     }
     pub async fn nvim_set_option(
-        &mut self,
+        &self,
         name: &str,
         value: &Object,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_option", args![name, value]).await
     }
-    pub async fn nvim_get_option(
-        &mut self,
-        name: &str,
-    ) -> Result<CallResponse<Object>, WriteError> {
+    pub async fn nvim_get_option(&self, name: &str) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_get_option", args![name]).await
     }
     pub async fn nvim_buf_get_option(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_buf_get_option", args![buffer, name]).await
     }
     pub async fn nvim_buf_set_option(
-        &mut self,
+        &self,
         buffer: &Buffer,
         name: &str,
         value: &Object,
@@ -471,14 +517,14 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_win_get_option(
-        &mut self,
+        &self,
         window: &Window,
         name: &str,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_win_get_option", args![window, name]).await
     }
     pub async fn nvim_win_set_option(
-        &mut self,
+        &self,
         window: &Window,
         name: &str,
         value: &Object,
@@ -487,13 +533,13 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_tabpage_list_wins(
-        &mut self,
+        &self,
         tabpage: &Tabpage,
     ) -> Result<CallResponse<Vec<Window>>, WriteError> {
         self.call("nvim_tabpage_list_wins", args![tabpage]).await
     }
     pub async fn nvim_tabpage_get_var(
-        &mut self,
+        &self,
         tabpage: &Tabpage,
         name: &str,
     ) -> Result<CallResponse<Object>, WriteError> {
@@ -501,7 +547,7 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_tabpage_set_var(
-        &mut self,
+        &self,
         tabpage: &Tabpage,
         name: &str,
         value: &Object,
@@ -510,7 +556,7 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_tabpage_del_var(
-        &mut self,
+        &self,
         tabpage: &Tabpage,
         name: &str,
     ) -> Result<CallResponse<()>, WriteError> {
@@ -518,25 +564,25 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_tabpage_get_win(
-        &mut self,
+        &self,
         tabpage: &Tabpage,
     ) -> Result<CallResponse<Window>, WriteError> {
         self.call("nvim_tabpage_get_win", args![tabpage]).await
     }
     pub async fn nvim_tabpage_get_number(
-        &mut self,
+        &self,
         tabpage: &Tabpage,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_tabpage_get_number", args![tabpage]).await
     }
     pub async fn nvim_tabpage_is_valid(
-        &mut self,
+        &self,
         tabpage: &Tabpage,
     ) -> Result<CallResponse<bool>, WriteError> {
         self.call("nvim_tabpage_is_valid", args![tabpage]).await
     }
     pub async fn nvim_ui_attach(
-        &mut self,
+        &self,
         width: i64,
         height: i64,
         options: UiOptions,
@@ -544,25 +590,25 @@ impl<W: RpcWriter> Client<W> {
         self.call("nvim_ui_attach", args![width, height, options])
             .await
     }
-    pub async fn nvim_ui_detach(&mut self) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_ui_detach(&self) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_ui_detach", args![]).await
     }
     pub async fn nvim_ui_try_resize(
-        &mut self,
+        &self,
         width: i64,
         height: i64,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_ui_try_resize", args![width, height]).await
     }
     pub async fn nvim_ui_set_option(
-        &mut self,
+        &self,
         name: &str,
         value: &Object,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_ui_set_option", args![name, value]).await
     }
     pub async fn nvim_ui_try_resize_grid(
-        &mut self,
+        &self,
         grid: i64,
         width: i64,
         height: i64,
@@ -571,13 +617,13 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_ui_pum_set_height(
-        &mut self,
+        &self,
         height: i64,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_ui_pum_set_height", args![height]).await
     }
     pub async fn nvim_ui_pum_set_bounds(
-        &mut self,
+        &self,
         width: f64,
         height: f64,
         row: f64,
@@ -587,44 +633,41 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_get_hl_by_name(
-        &mut self,
+        &self,
         name: &str,
         rgb: bool,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_hl_by_name", args![name, rgb]).await
     }
     pub async fn nvim_get_hl_by_id(
-        &mut self,
+        &self,
         hl_id: i64,
         rgb: bool,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_hl_by_id", args![hl_id, rgb]).await
     }
     pub async fn nvim_get_hl_id_by_name(
-        &mut self,
+        &self,
         name: &str,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_get_hl_id_by_name", args![name]).await
     }
     pub async fn nvim_set_hl(
-        &mut self,
+        &self,
         ns_id: i64,
         name: &str,
-        val: &Dictionary,
+        val: &keysets::Highlight,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_hl", args![ns_id, name, val]).await
     }
-    pub async fn nvim_set_hl_ns(&mut self, ns_id: i64) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_set_hl_ns(&self, ns_id: i64) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_hl_ns", args![ns_id]).await
     }
-    pub async fn nvim_set_hl_ns_fast(
-        &mut self,
-        ns_id: i64,
-    ) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_set_hl_ns_fast(&self, ns_id: i64) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_hl_ns_fast", args![ns_id]).await
     }
     pub async fn nvim_feedkeys(
-        &mut self,
+        &self,
         keys: &str,
         mode: &str,
         escape_ks: bool,
@@ -632,11 +675,11 @@ impl<W: RpcWriter> Client<W> {
         self.call("nvim_feedkeys", args![keys, mode, escape_ks])
             .await
     }
-    pub async fn nvim_input(&mut self, keys: &str) -> Result<CallResponse<i64>, WriteError> {
+    pub async fn nvim_input(&self, keys: &str) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_input", args![keys]).await
     }
     pub async fn nvim_input_mouse(
-        &mut self,
+        &self,
         button: &str,
         action: &str,
         modifier: &str,
@@ -651,7 +694,7 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_replace_termcodes(
-        &mut self,
+        &self,
         str: &str,
         from_part: bool,
         do_lt: bool,
@@ -664,152 +707,152 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_exec_lua(
-        &mut self,
+        &self,
         code: &str,
         args: Vec<rmpv::Value>,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_exec_lua", args![code, args]).await
     }
+    #[deprecated(note = "deprecated since api level 7")]
+    pub async fn nvim_execute_lua(
+        &self,
+        code: &str,
+        args: Vec<rmpv::Value>,
+    ) -> Result<CallResponse<Object>, WriteError> {
+        self.call("nvim_execute_lua", args![code, args]).await
+    }
     pub async fn nvim_notify(
-        &mut self,
+        &self,
         msg: &str,
         log_level: i64,
         opts: &Dictionary,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_notify", args![msg, log_level, opts]).await
     }
-    pub async fn nvim_strwidth(&mut self, text: &str) -> Result<CallResponse<i64>, WriteError> {
+    pub async fn nvim_strwidth(&self, text: &str) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_strwidth", args![text]).await
     }
-    pub async fn nvim_list_runtime_paths(
-        &mut self,
-    ) -> Result<CallResponse<Vec<String>>, WriteError> {
+    pub async fn nvim_list_runtime_paths(&self) -> Result<CallResponse<Vec<String>>, WriteError> {
         self.call("nvim_list_runtime_paths", args![]).await
     }
     pub async fn nvim_get_runtime_file(
-        &mut self,
+        &self,
         name: &str,
         all: bool,
     ) -> Result<CallResponse<Vec<String>>, WriteError> {
         self.call("nvim_get_runtime_file", args![name, all]).await
     }
-    pub async fn nvim_set_current_dir(
-        &mut self,
-        dir: &str,
-    ) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_set_current_dir(&self, dir: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_current_dir", args![dir]).await
     }
-    pub async fn nvim_get_current_line(&mut self) -> Result<CallResponse<String>, WriteError> {
+    pub async fn nvim_get_current_line(&self) -> Result<CallResponse<String>, WriteError> {
         self.call("nvim_get_current_line", args![]).await
     }
-    pub async fn nvim_set_current_line(
-        &mut self,
-        line: &str,
-    ) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_set_current_line(&self, line: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_current_line", args![line]).await
     }
-    pub async fn nvim_del_current_line(&mut self) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_del_current_line(&self) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_del_current_line", args![]).await
     }
-    pub async fn nvim_get_var(&mut self, name: &str) -> Result<CallResponse<Object>, WriteError> {
+    pub async fn nvim_get_var(&self, name: &str) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_get_var", args![name]).await
     }
     pub async fn nvim_set_var(
-        &mut self,
+        &self,
         name: &str,
         value: &Object,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_var", args![name, value]).await
     }
-    pub async fn nvim_del_var(&mut self, name: &str) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_del_var(&self, name: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_del_var", args![name]).await
     }
-    pub async fn nvim_get_vvar(&mut self, name: &str) -> Result<CallResponse<Object>, WriteError> {
+    pub async fn nvim_get_vvar(&self, name: &str) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_get_vvar", args![name]).await
     }
     pub async fn nvim_set_vvar(
-        &mut self,
+        &self,
         name: &str,
         value: &Object,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_vvar", args![name, value]).await
     }
     pub async fn nvim_echo(
-        &mut self,
+        &self,
         chunks: Vec<rmpv::Value>,
         history: bool,
         opts: &Dictionary,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_echo", args![chunks, history, opts]).await
     }
-    pub async fn nvim_out_write(&mut self, str: &str) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_out_write(&self, str: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_out_write", args![str]).await
     }
-    pub async fn nvim_err_write(&mut self, str: &str) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_err_write(&self, str: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_err_write", args![str]).await
     }
-    pub async fn nvim_err_writeln(&mut self, str: &str) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_err_writeln(&self, str: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_err_writeln", args![str]).await
     }
-    pub async fn nvim_list_bufs(&mut self) -> Result<CallResponse<Vec<Buffer>>, WriteError> {
+    pub async fn nvim_list_bufs(&self) -> Result<CallResponse<Vec<Buffer>>, WriteError> {
         self.call("nvim_list_bufs", args![]).await
     }
-    pub async fn nvim_get_current_buf(&mut self) -> Result<CallResponse<Buffer>, WriteError> {
+    pub async fn nvim_get_current_buf(&self) -> Result<CallResponse<Buffer>, WriteError> {
         self.call("nvim_get_current_buf", args![]).await
     }
     pub async fn nvim_set_current_buf(
-        &mut self,
+        &self,
         buffer: &Buffer,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_current_buf", args![buffer]).await
     }
-    pub async fn nvim_list_wins(&mut self) -> Result<CallResponse<Vec<Window>>, WriteError> {
+    pub async fn nvim_list_wins(&self) -> Result<CallResponse<Vec<Window>>, WriteError> {
         self.call("nvim_list_wins", args![]).await
     }
-    pub async fn nvim_get_current_win(&mut self) -> Result<CallResponse<Window>, WriteError> {
+    pub async fn nvim_get_current_win(&self) -> Result<CallResponse<Window>, WriteError> {
         self.call("nvim_get_current_win", args![]).await
     }
     pub async fn nvim_set_current_win(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_current_win", args![window]).await
     }
     pub async fn nvim_create_buf(
-        &mut self,
+        &self,
         listed: bool,
         scratch: bool,
     ) -> Result<CallResponse<Buffer>, WriteError> {
         self.call("nvim_create_buf", args![listed, scratch]).await
     }
     pub async fn nvim_open_term(
-        &mut self,
+        &self,
         buffer: &Buffer,
         opts: &Dictionary,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_open_term", args![buffer, opts]).await
     }
     pub async fn nvim_chan_send(
-        &mut self,
+        &self,
         chan: i64,
         data: &str,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_chan_send", args![chan, data]).await
     }
-    pub async fn nvim_list_tabpages(&mut self) -> Result<CallResponse<Vec<Tabpage>>, WriteError> {
+    pub async fn nvim_list_tabpages(&self) -> Result<CallResponse<Vec<Tabpage>>, WriteError> {
         self.call("nvim_list_tabpages", args![]).await
     }
-    pub async fn nvim_get_current_tabpage(&mut self) -> Result<CallResponse<Tabpage>, WriteError> {
+    pub async fn nvim_get_current_tabpage(&self) -> Result<CallResponse<Tabpage>, WriteError> {
         self.call("nvim_get_current_tabpage", args![]).await
     }
     pub async fn nvim_set_current_tabpage(
-        &mut self,
+        &self,
         tabpage: &Tabpage,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_current_tabpage", args![tabpage]).await
     }
     pub async fn nvim_paste(
-        &mut self,
+        &self,
         data: &str,
         crlf: bool,
         phase: i64,
@@ -817,7 +860,7 @@ impl<W: RpcWriter> Client<W> {
         self.call("nvim_paste", args![data, crlf, phase]).await
     }
     pub async fn nvim_put(
-        &mut self,
+        &self,
         lines: Vec<String>,
         _type: &str,
         after: bool,
@@ -826,66 +869,64 @@ impl<W: RpcWriter> Client<W> {
         self.call("nvim_put", args![lines, _type, after, follow])
             .await
     }
-    pub async fn nvim_subscribe(&mut self, event: &str) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_subscribe(&self, event: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_subscribe", args![event]).await
     }
-    pub async fn nvim_unsubscribe(&mut self, event: &str) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_unsubscribe(&self, event: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_unsubscribe", args![event]).await
     }
     pub async fn nvim_get_color_by_name(
-        &mut self,
+        &self,
         name: &str,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_get_color_by_name", args![name]).await
     }
-    pub async fn nvim_get_color_map(&mut self) -> Result<CallResponse<Dictionary>, WriteError> {
+    pub async fn nvim_get_color_map(&self) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_color_map", args![]).await
     }
     pub async fn nvim_get_context(
-        &mut self,
-        opts: &Dictionary,
+        &self,
+        opts: &keysets::Context,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_context", args![opts]).await
     }
     pub async fn nvim_load_context(
-        &mut self,
+        &self,
         dict: &Dictionary,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_load_context", args![dict]).await
     }
-    pub async fn nvim_get_mode(&mut self) -> Result<CallResponse<Dictionary>, WriteError> {
+    pub async fn nvim_get_mode(&self) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_mode", args![]).await
     }
     pub async fn nvim_get_keymap(
-        &mut self,
+        &self,
         mode: &str,
     ) -> Result<CallResponse<Vec<Dictionary>>, WriteError> {
         self.call("nvim_get_keymap", args![mode]).await
     }
     pub async fn nvim_set_keymap(
-        &mut self,
+        &self,
         mode: &str,
         lhs: &str,
         rhs: &str,
-        opts: &Dictionary,
+        opts: &keysets::Keymap,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_set_keymap", args![mode, lhs, rhs, opts])
             .await
     }
     pub async fn nvim_del_keymap(
-        &mut self,
+        &self,
         mode: &str,
         lhs: &str,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_del_keymap", args![mode, lhs]).await
     }
-    pub async fn nvim_get_api_info(
-        &mut self,
-    ) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
+    pub async fn nvim_get_api_info(&self) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
         self.call("nvim_get_api_info", args![]).await
     }
     pub async fn nvim_set_client_info(
-        &mut self,
+        &self,
         name: &str,
         version: &Dictionary,
         _type: &str,
@@ -899,34 +940,34 @@ impl<W: RpcWriter> Client<W> {
         .await
     }
     pub async fn nvim_get_chan_info(
-        &mut self,
+        &self,
         chan: i64,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_get_chan_info", args![chan]).await
     }
-    pub async fn nvim_list_chans(&mut self) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
+    pub async fn nvim_list_chans(&self) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
         self.call("nvim_list_chans", args![]).await
     }
     pub async fn nvim_call_atomic(
-        &mut self,
+        &self,
         calls: Vec<rmpv::Value>,
     ) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
         self.call("nvim_call_atomic", args![calls]).await
     }
-    pub async fn nvim_list_uis(&mut self) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
+    pub async fn nvim_list_uis(&self) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
         self.call("nvim_list_uis", args![]).await
     }
     pub async fn nvim_get_proc_children(
-        &mut self,
+        &self,
         pid: i64,
     ) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
         self.call("nvim_get_proc_children", args![pid]).await
     }
-    pub async fn nvim_get_proc(&mut self, pid: i64) -> Result<CallResponse<Object>, WriteError> {
+    pub async fn nvim_get_proc(&self, pid: i64) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_get_proc", args![pid]).await
     }
     pub async fn nvim_select_popupmenu_item(
-        &mut self,
+        &self,
         item: i64,
         insert: bool,
         finish: bool,
@@ -938,45 +979,52 @@ impl<W: RpcWriter> Client<W> {
         )
         .await
     }
-    pub async fn nvim_del_mark(&mut self, name: &str) -> Result<CallResponse<bool>, WriteError> {
+    pub async fn nvim_del_mark(&self, name: &str) -> Result<CallResponse<bool>, WriteError> {
         self.call("nvim_del_mark", args![name]).await
     }
     pub async fn nvim_get_mark(
-        &mut self,
+        &self,
         name: &str,
         opts: &Dictionary,
     ) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
         self.call("nvim_get_mark", args![name, opts]).await
     }
     pub async fn nvim_eval_statusline(
-        &mut self,
+        &self,
         str: &str,
-        opts: &Dictionary,
+        opts: &keysets::EvalStatusline,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_eval_statusline", args![str, opts]).await
     }
     pub async fn nvim_exec(
-        &mut self,
+        &self,
         src: &str,
         output: bool,
     ) -> Result<CallResponse<String>, WriteError> {
         self.call("nvim_exec", args![src, output]).await
     }
-    pub async fn nvim_command(&mut self, command: &str) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_command(&self, command: &str) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_command", args![command]).await
     }
-    pub async fn nvim_eval(&mut self, expr: &str) -> Result<CallResponse<Object>, WriteError> {
+    #[deprecated(note = "deprecated since api level 7")]
+    pub async fn nvim_command_output(
+        &self,
+        command: &str,
+    ) -> Result<CallResponse<String>, WriteError> {
+        self.call("nvim_command_output", args![command]).await
+    }
+    pub async fn nvim_eval(&self, expr: &str) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_eval", args![expr]).await
     }
     pub async fn nvim_call_function(
-        &mut self,
+        &self,
         _fn: &str,
         args: Vec<rmpv::Value>,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_call_function", args![_fn, args]).await
     }
     pub async fn nvim_call_dict_function(
-        &mut self,
+        &self,
         dict: &Object,
         _fn: &str,
         args: Vec<rmpv::Value>,
@@ -985,7 +1033,7 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_parse_expression(
-        &mut self,
+        &self,
         expr: &str,
         flags: &str,
         highlight: bool,
@@ -994,62 +1042,62 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_open_win(
-        &mut self,
+        &self,
         buffer: &Buffer,
         enter: bool,
-        config: &Dictionary,
+        config: &keysets::FloatConfig,
     ) -> Result<CallResponse<Window>, WriteError> {
         self.call("nvim_open_win", args![buffer, enter, config])
             .await
     }
     pub async fn nvim_win_set_config(
-        &mut self,
+        &self,
         window: &Window,
-        config: &Dictionary,
+        config: &keysets::FloatConfig,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_win_set_config", args![window, config])
             .await
     }
     pub async fn nvim_win_get_config(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<Dictionary>, WriteError> {
         self.call("nvim_win_get_config", args![window]).await
     }
     pub async fn nvim_win_get_buf(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<Buffer>, WriteError> {
         self.call("nvim_win_get_buf", args![window]).await
     }
     pub async fn nvim_win_set_buf(
-        &mut self,
+        &self,
         window: &Window,
         buffer: &Buffer,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_win_set_buf", args![window, buffer]).await
     }
     pub async fn nvim_win_get_cursor(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<(i64, i64)>, WriteError> {
         self.call("nvim_win_get_cursor", args![window]).await
     }
     pub async fn nvim_win_set_cursor(
-        &mut self,
+        &self,
         window: &Window,
         pos: (i64, i64),
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_win_set_cursor", args![window, pos]).await
     }
     pub async fn nvim_win_get_height(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_win_get_height", args![window]).await
     }
     pub async fn nvim_win_set_height(
-        &mut self,
+        &self,
         window: &Window,
         height: i64,
     ) -> Result<CallResponse<()>, WriteError> {
@@ -1057,27 +1105,27 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_win_get_width(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_win_get_width", args![window]).await
     }
     pub async fn nvim_win_set_width(
-        &mut self,
+        &self,
         window: &Window,
         width: i64,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_win_set_width", args![window, width]).await
     }
     pub async fn nvim_win_get_var(
-        &mut self,
+        &self,
         window: &Window,
         name: &str,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_win_get_var", args![window, name]).await
     }
     pub async fn nvim_win_set_var(
-        &mut self,
+        &self,
         window: &Window,
         name: &str,
         value: &Object,
@@ -1086,58 +1134,601 @@ impl<W: RpcWriter> Client<W> {
             .await
     }
     pub async fn nvim_win_del_var(
-        &mut self,
+        &self,
         window: &Window,
         name: &str,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_win_del_var", args![window, name]).await
     }
     pub async fn nvim_win_get_position(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<(i64, i64)>, WriteError> {
         self.call("nvim_win_get_position", args![window]).await
     }
     pub async fn nvim_win_get_tabpage(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<Tabpage>, WriteError> {
         self.call("nvim_win_get_tabpage", args![window]).await
     }
     pub async fn nvim_win_get_number(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<i64>, WriteError> {
         self.call("nvim_win_get_number", args![window]).await
     }
     pub async fn nvim_win_is_valid(
-        &mut self,
+        &self,
         window: &Window,
     ) -> Result<CallResponse<bool>, WriteError> {
         self.call("nvim_win_is_valid", args![window]).await
     }
-    pub async fn nvim_win_hide(&mut self, window: &Window) -> Result<CallResponse<()>, WriteError> {
+    pub async fn nvim_win_hide(&self, window: &Window) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_win_hide", args![window]).await
     }
     pub async fn nvim_win_close(
-        &mut self,
+        &self,
         window: &Window,
         force: bool,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_win_close", args![window, force]).await
     }
     pub async fn nvim_win_call(
-        &mut self,
+        &self,
         window: &Window,
         fun: &LuaRef,
     ) -> Result<CallResponse<Object>, WriteError> {
         self.call("nvim_win_call", args![window, fun]).await
     }
     pub async fn nvim_win_set_hl_ns(
-        &mut self,
+        &self,
         window: &Window,
         ns_id: i64,
     ) -> Result<CallResponse<()>, WriteError> {
         self.call("nvim_win_set_hl_ns", args![window, ns_id]).await
     }
 }
+impl Buffer {
+    pub async fn line_count(&self, client: &Client) -> Result<CallResponse<i64>, WriteError> {
+        client.call("nvim_buf_line_count", args![self]).await
+    }
+    pub async fn attach(
+        &self,
+        client: &Client,
+        send_buffer: bool,
+        opts: &Dictionary,
+    ) -> Result<CallResponse<bool>, WriteError> {
+        client
+            .call("nvim_buf_attach", args![self, send_buffer, opts])
+            .await
+    }
+    pub async fn detach(&self, client: &Client) -> Result<CallResponse<bool>, WriteError> {
+        client.call("nvim_buf_detach", args![self]).await
+    }
+    pub async fn get_lines(
+        &self,
+        client: &Client,
+        start: i64,
+        end: i64,
+        strict_indexing: bool,
+    ) -> Result<CallResponse<Vec<String>>, WriteError> {
+        client
+            .call(
+                "nvim_buf_get_lines",
+                args![self, start, end, strict_indexing],
+            )
+            .await
+    }
+    pub async fn set_lines(
+        &self,
+        client: &Client,
+        start: i64,
+        end: i64,
+        strict_indexing: bool,
+        replacement: Vec<String>,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call(
+                "nvim_buf_set_lines",
+                args![self, start, end, strict_indexing, replacement],
+            )
+            .await
+    }
+    pub async fn set_text(
+        &self,
+        client: &Client,
+        start_row: i64,
+        start_col: i64,
+        end_row: i64,
+        end_col: i64,
+        replacement: Vec<String>,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call(
+                "nvim_buf_set_text",
+                args![self, start_row, start_col, end_row, end_col, replacement],
+            )
+            .await
+    }
+    pub async fn get_text(
+        &self,
+        client: &Client,
+        start_row: i64,
+        start_col: i64,
+        end_row: i64,
+        end_col: i64,
+        opts: &Dictionary,
+    ) -> Result<CallResponse<Vec<String>>, WriteError> {
+        client
+            .call(
+                "nvim_buf_get_text",
+                args![self, start_row, start_col, end_row, end_col, opts],
+            )
+            .await
+    }
+    pub async fn get_offset(
+        &self,
+        client: &Client,
+        index: i64,
+    ) -> Result<CallResponse<i64>, WriteError> {
+        client.call("nvim_buf_get_offset", args![self, index]).await
+    }
+    pub async fn get_var(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<Object>, WriteError> {
+        client.call("nvim_buf_get_var", args![self, name]).await
+    }
+    pub async fn get_changedtick(&self, client: &Client) -> Result<CallResponse<i64>, WriteError> {
+        client.call("nvim_buf_get_changedtick", args![self]).await
+    }
+    pub async fn get_keymap(
+        &self,
+        client: &Client,
+        mode: &str,
+    ) -> Result<CallResponse<Vec<Dictionary>>, WriteError> {
+        client.call("nvim_buf_get_keymap", args![self, mode]).await
+    }
+    pub async fn set_keymap(
+        &self,
+        client: &Client,
+        mode: &str,
+        lhs: &str,
+        rhs: &str,
+        opts: &keysets::Keymap,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_buf_set_keymap", args![self, mode, lhs, rhs, opts])
+            .await
+    }
+    pub async fn del_keymap(
+        &self,
+        client: &Client,
+        mode: &str,
+        lhs: &str,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_buf_del_keymap", args![self, mode, lhs])
+            .await
+    }
+    pub async fn set_var(
+        &self,
+        client: &Client,
+        name: &str,
+        value: &Object,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_buf_set_var", args![self, name, value])
+            .await
+    }
+    pub async fn del_var(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_buf_del_var", args![self, name]).await
+    }
+    pub async fn get_name(&self, client: &Client) -> Result<CallResponse<String>, WriteError> {
+        client.call("nvim_buf_get_name", args![self]).await
+    }
+    #[deprecated(note = "deprecated since api level 2")]
+    pub async fn get_number(&self, client: &Client) -> Result<CallResponse<i64>, WriteError> {
+        client.call("nvim_buf_get_number", args![self]).await
+    }
+    pub async fn set_name(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_buf_set_name", args![self, name]).await
+    }
+    pub async fn is_loaded(&self, client: &Client) -> Result<CallResponse<bool>, WriteError> {
+        client.call("nvim_buf_is_loaded", args![self]).await
+    }
+    pub async fn delete(
+        &self,
+        client: &Client,
+        opts: &Dictionary,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_buf_delete", args![self, opts]).await
+    }
+    pub async fn is_valid(&self, client: &Client) -> Result<CallResponse<bool>, WriteError> {
+        client.call("nvim_buf_is_valid", args![self]).await
+    }
+    pub async fn del_mark(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<bool>, WriteError> {
+        client.call("nvim_buf_del_mark", args![self, name]).await
+    }
+    pub async fn set_mark(
+        &self,
+        client: &Client,
+        name: &str,
+        line: i64,
+        col: i64,
+        opts: &Dictionary,
+    ) -> Result<CallResponse<bool>, WriteError> {
+        client
+            .call("nvim_buf_set_mark", args![self, name, line, col, opts])
+            .await
+    }
+    pub async fn get_mark(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<(i64, i64)>, WriteError> {
+        client.call("nvim_buf_get_mark", args![self, name]).await
+    }
+    pub async fn call(
+        &self,
+        client: &Client,
+        fun: &LuaRef,
+    ) -> Result<CallResponse<Object>, WriteError> {
+        client.call("nvim_buf_call", args![self, fun]).await
+    }
+    pub async fn create_user_command(
+        &self,
+        client: &Client,
+        name: &str,
+        command: &Object,
+        opts: &keysets::UserCommand,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call(
+                "nvim_buf_create_user_command",
+                args![self, name, command, opts],
+            )
+            .await
+    }
+    pub async fn del_user_command(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_buf_del_user_command", args![self, name])
+            .await
+    }
+    pub async fn get_commands(
+        &self,
+        client: &Client,
+        opts: &keysets::GetCommands,
+    ) -> Result<CallResponse<Dictionary>, WriteError> {
+        client
+            .call("nvim_buf_get_commands", args![self, opts])
+            .await
+    }
+    pub async fn get_extmark_by_id(
+        &self,
+        client: &Client,
+        ns_id: i64,
+        id: i64,
+        opts: &Dictionary,
+    ) -> Result<CallResponse<Vec<i64>>, WriteError> {
+        client
+            .call("nvim_buf_get_extmark_by_id", args![self, ns_id, id, opts])
+            .await
+    }
+    pub async fn get_extmarks(
+        &self,
+        client: &Client,
+        ns_id: i64,
+        start: &Object,
+        end: &Object,
+        opts: &Dictionary,
+    ) -> Result<CallResponse<Vec<rmpv::Value>>, WriteError> {
+        client
+            .call(
+                "nvim_buf_get_extmarks",
+                args![self, ns_id, start, end, opts],
+            )
+            .await
+    }
+    pub async fn set_extmark(
+        &self,
+        client: &Client,
+        ns_id: i64,
+        line: i64,
+        col: i64,
+        opts: &keysets::SetExtmark,
+    ) -> Result<CallResponse<i64>, WriteError> {
+        client
+            .call("nvim_buf_set_extmark", args![self, ns_id, line, col, opts])
+            .await
+    }
+    #[deprecated(note = "deprecated since api level 8")]
+    pub async fn set_virtual_text(
+        &self,
+        client: &Client,
+        src_id: i64,
+        line: i64,
+        chunks: Vec<rmpv::Value>,
+        opts: &Dictionary,
+    ) -> Result<CallResponse<i64>, WriteError> {
+        client
+            .call(
+                "nvim_buf_set_virtual_text",
+                args![self, src_id, line, chunks, opts],
+            )
+            .await
+    }
+    pub async fn del_extmark(
+        &self,
+        client: &Client,
+        ns_id: i64,
+        id: i64,
+    ) -> Result<CallResponse<bool>, WriteError> {
+        client
+            .call("nvim_buf_del_extmark", args![self, ns_id, id])
+            .await
+    }
+    pub async fn add_highlight(
+        &self,
+        client: &Client,
+        ns_id: i64,
+        hl_group: &str,
+        line: i64,
+        col_start: i64,
+        col_end: i64,
+    ) -> Result<CallResponse<i64>, WriteError> {
+        client
+            .call(
+                "nvim_buf_add_highlight",
+                args![self, ns_id, hl_group, line, col_start, col_end],
+            )
+            .await
+    }
+    pub async fn clear_namespace(
+        &self,
+        client: &Client,
+        ns_id: i64,
+        line_start: i64,
+        line_end: i64,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call(
+                "nvim_buf_clear_namespace",
+                args![self, ns_id, line_start, line_end],
+            )
+            .await
+    }
+    #[deprecated(note = "deprecated since api level 7")]
+    pub async fn clear_highlight(
+        &self,
+        client: &Client,
+        ns_id: i64,
+        line_start: i64,
+        line_end: i64,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call(
+                "nvim_buf_clear_highlight",
+                args![self, ns_id, line_start, line_end],
+            )
+            .await
+    }
+    pub async fn get_option(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<Object>, WriteError> {
+        client.call("nvim_buf_get_option", args![self, name]).await
+    }
+    pub async fn set_option(
+        &self,
+        client: &Client,
+        name: &str,
+        value: &Object,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_buf_set_option", args![self, name, value])
+            .await
+    }
+}
+impl Window {
+    pub async fn get_option(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<Object>, WriteError> {
+        client.call("nvim_win_get_option", args![self, name]).await
+    }
+    pub async fn set_option(
+        &self,
+        client: &Client,
+        name: &str,
+        value: &Object,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_win_set_option", args![self, name, value])
+            .await
+    }
+    pub async fn set_config(
+        &self,
+        client: &Client,
+        config: &keysets::FloatConfig,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_win_set_config", args![self, config])
+            .await
+    }
+    pub async fn get_config(
+        &self,
+        client: &Client,
+    ) -> Result<CallResponse<Dictionary>, WriteError> {
+        client.call("nvim_win_get_config", args![self]).await
+    }
+    pub async fn get_buf(&self, client: &Client) -> Result<CallResponse<Buffer>, WriteError> {
+        client.call("nvim_win_get_buf", args![self]).await
+    }
+    pub async fn set_buf(
+        &self,
+        client: &Client,
+        buffer: &Buffer,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_win_set_buf", args![self, buffer]).await
+    }
+    pub async fn get_cursor(
+        &self,
+        client: &Client,
+    ) -> Result<CallResponse<(i64, i64)>, WriteError> {
+        client.call("nvim_win_get_cursor", args![self]).await
+    }
+    pub async fn set_cursor(
+        &self,
+        client: &Client,
+        pos: (i64, i64),
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_win_set_cursor", args![self, pos]).await
+    }
+    pub async fn get_height(&self, client: &Client) -> Result<CallResponse<i64>, WriteError> {
+        client.call("nvim_win_get_height", args![self]).await
+    }
+    pub async fn set_height(
+        &self,
+        client: &Client,
+        height: i64,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_win_set_height", args![self, height])
+            .await
+    }
+    pub async fn get_width(&self, client: &Client) -> Result<CallResponse<i64>, WriteError> {
+        client.call("nvim_win_get_width", args![self]).await
+    }
+    pub async fn set_width(
+        &self,
+        client: &Client,
+        width: i64,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_win_set_width", args![self, width]).await
+    }
+    pub async fn get_var(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<Object>, WriteError> {
+        client.call("nvim_win_get_var", args![self, name]).await
+    }
+    pub async fn set_var(
+        &self,
+        client: &Client,
+        name: &str,
+        value: &Object,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_win_set_var", args![self, name, value])
+            .await
+    }
+    pub async fn del_var(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_win_del_var", args![self, name]).await
+    }
+    pub async fn get_position(
+        &self,
+        client: &Client,
+    ) -> Result<CallResponse<(i64, i64)>, WriteError> {
+        client.call("nvim_win_get_position", args![self]).await
+    }
+    pub async fn get_tabpage(&self, client: &Client) -> Result<CallResponse<Tabpage>, WriteError> {
+        client.call("nvim_win_get_tabpage", args![self]).await
+    }
+    pub async fn get_number(&self, client: &Client) -> Result<CallResponse<i64>, WriteError> {
+        client.call("nvim_win_get_number", args![self]).await
+    }
+    pub async fn is_valid(&self, client: &Client) -> Result<CallResponse<bool>, WriteError> {
+        client.call("nvim_win_is_valid", args![self]).await
+    }
+    pub async fn hide(&self, client: &Client) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_win_hide", args![self]).await
+    }
+    pub async fn close(
+        &self,
+        client: &Client,
+        force: bool,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_win_close", args![self, force]).await
+    }
+    pub async fn call(
+        &self,
+        client: &Client,
+        fun: &LuaRef,
+    ) -> Result<CallResponse<Object>, WriteError> {
+        client.call("nvim_win_call", args![self, fun]).await
+    }
+    pub async fn set_hl_ns(
+        &self,
+        client: &Client,
+        ns_id: i64,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_win_set_hl_ns", args![self, ns_id]).await
+    }
+}
+impl Tabpage {
+    pub async fn list_wins(
+        &self,
+        client: &Client,
+    ) -> Result<CallResponse<Vec<Window>>, WriteError> {
+        client.call("nvim_tabpage_list_wins", args![self]).await
+    }
+    pub async fn get_var(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<Object>, WriteError> {
+        client.call("nvim_tabpage_get_var", args![self, name]).await
+    }
+    pub async fn set_var(
+        &self,
+        client: &Client,
+        name: &str,
+        value: &Object,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client
+            .call("nvim_tabpage_set_var", args![self, name, value])
+            .await
+    }
+    pub async fn del_var(
+        &self,
+        client: &Client,
+        name: &str,
+    ) -> Result<CallResponse<()>, WriteError> {
+        client.call("nvim_tabpage_del_var", args![self, name]).await
+    }
+    pub async fn get_win(&self, client: &Client) -> Result<CallResponse<Window>, WriteError> {
+        client.call("nvim_tabpage_get_win", args![self]).await
+    }
+    pub async fn get_number(&self, client: &Client) -> Result<CallResponse<i64>, WriteError> {
+        client.call("nvim_tabpage_get_number", args![self]).await
+    }
+    pub async fn is_valid(&self, client: &Client) -> Result<CallResponse<bool>, WriteError> {
+        client.call("nvim_tabpage_is_valid", args![self]).await
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+
+use crate::rpc::message::Request;
+use crate::NvimErrorKind;
+
+/// Result of a handled request. The `Ok` value is sent back as the response's
+/// result, and the `Err` value as the response's error.
+pub type HandlerResult = Result<rmpv::Value, rmpv::Value>;
+
+/// Handler for a msgpack-rpc request coming from neovim (e.g. through
+/// `rpcrequest()`).
+#[async_trait::async_trait(?Send)]
+pub trait RequestHandler {
+    async fn handle_request(&self, params: rmpv::Value) -> HandlerResult;
+}
+
+#[async_trait::async_trait(?Send)]
+impl<F> RequestHandler for F
+where
+    F: Fn(rmpv::Value) -> HandlerResult,
+{
+    async fn handle_request(&self, params: rmpv::Value) -> HandlerResult {
+        self(params)
+    }
+}
+
+/// Creates an error value in the `[type, message]` format neovim uses for its
+/// own errors, so the message is shown correctly on the neovim side.
+pub fn error_value<S: Into<String>>(msg: S) -> rmpv::Value {
+    rmpv::Value::Array(vec![
+        NvimErrorKind::Exception.id().into(),
+        msg.into().into(),
+    ])
+}
+
+/// Collection of request handlers, keyed by the request's method name.
+#[derive(Default)]
+pub struct RequestHandlers {
+    handlers: HashMap<String, Rc<dyn RequestHandler>>,
+}
+
+impl RequestHandlers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register handler for `method`. Replaces any existing handler for the
+    /// same method.
+    pub fn register<S, H>(&mut self, method: S, handler: H)
+    where
+        S: Into<String>,
+        H: RequestHandler + 'static,
+    {
+        self.handlers.insert(method.into(), Rc::new(handler));
+    }
+
+    /// Remove the handler for `method`, returning if one was registered.
+    pub fn unregister(&mut self, method: &str) -> bool {
+        self.handlers.remove(method).is_some()
+    }
+
+    /// Handle the request with its registered handler. If no handler is
+    /// registered for the request's method, an error is returned.
+    ///
+    /// The result should be written back with `Client::respond`. The
+    /// returned future doesn't borrow the handlers, so they can be changed
+    /// while the request is being handled.
+    pub fn handle(&self, request: Request) -> impl Future<Output = HandlerResult> + 'static {
+        let handler = self.handlers.get(&request.method).cloned();
+
+        async move {
+            match handler {
+                Some(handler) => handler.handle_request(request.params).await,
+                None => Err(error_value(format!(
+                    "unknown request method: {}",
+                    request.method
+                ))),
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for RequestHandlers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestHandlers")
+            .field("methods", &self.handlers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -1,10 +1,22 @@
+pub mod batch;
 pub mod client;
 mod gen;
+pub mod handler;
 pub mod rpc;
+pub mod screen;
+pub mod session;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod transport;
 pub mod types;
 
-pub use client::{CallError, CallResponse, Client, HandleError};
+pub use batch::Batch;
+pub use client::{CallError, CallResponse, Client, HandleError, NvimError, NvimErrorKind};
+/// Api level the bindings are generated for, nvim needs to support it.
+pub use gen::MIN_API_LEVEL;
+pub use handler::{RequestHandler, RequestHandlers};
 pub use rpc::RpcWriter;
+pub use session::{Incoming, Session};
 pub use types::decode_redraw_params;
 
 // NOTE(ville): re-export serde.
@@ -1,4 +1,4 @@
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
 #[serde(untagged)]
 pub enum Message {
     Request(Request),
@@ -20,9 +20,14 @@ impl Message {
             _ => None,
         }
     }
+
+    /// Encodes the message in the msgpack-rpc wire format.
+    pub fn to_vec(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec(self)
+    }
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct Request {
     // NOTE(ville): Required for deserialization.
     #[allow(dead_code)]
@@ -33,7 +38,18 @@ pub struct Request {
     pub params: rmpv::Value,
 }
 
-#[derive(Debug, serde::Deserialize)]
+impl Request {
+    pub fn new<S: Into<String>>(msgid: u32, method: S, params: rmpv::Value) -> Self {
+        Self {
+            r#type: 0,
+            msgid,
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct Response {
     // NOTE(ville): Required for deserialization.
     #[allow(dead_code)]
@@ -44,7 +60,18 @@ pub struct Response {
     pub result: Option<rmpv::Value>,
 }
 
-#[derive(Debug, serde::Deserialize)]
+impl Response {
+    pub fn new(msgid: u32, error: Option<rmpv::Value>, result: Option<rmpv::Value>) -> Self {
+        Self {
+            r#type: 1,
+            msgid,
+            error,
+            result,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct Notification {
     // NOTE(ville): Required for deserialization.
     #[allow(dead_code)]
@@ -53,3 +80,13 @@ pub struct Notification {
     pub method: String,
     pub params: rmpv::Value,
 }
+
+impl Notification {
+    pub fn new<S: Into<String>>(method: S, params: rmpv::Value) -> Self {
+        Self {
+            r#type: 2,
+            method: method.into(),
+            params,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod message;
 pub mod reader;
+pub mod record;
 pub mod writer;
 
 pub use message::Message;
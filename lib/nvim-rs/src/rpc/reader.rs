@@ -10,12 +10,24 @@ pub enum ReadError {
     RmpError(rmp_serde::decode::Error),
 }
 
+/// Size of a single read from the underlying reader.
+const READ_SIZE: usize = 32 * 1024;
+
 pub struct RpcReader<R>
 where
     R: AsyncRead + Unpin,
 {
-    reader: futures::io::BufReader<R>,
+    reader: R,
+    /// Buffer for the received data. Reused for all the messages.
     buf: Vec<u8>,
+    /// End of the received data in `buf`.
+    end: usize,
+    /// Start of the current message in `buf`.
+    start: usize,
+    /// Length of the previously returned message, which is dropped from the
+    /// buffer on the next read.
+    consumed: usize,
+    scanner: Scanner,
 }
 
 impl<R> RpcReader<R>
@@ -24,28 +36,41 @@ where
 {
     pub fn new(reader: R) -> Self {
         Self {
-            reader: futures::io::BufReader::new(reader),
+            reader,
             buf: Vec::new(),
+            end: 0,
+            start: 0,
+            consumed: 0,
+            scanner: Scanner::new(),
         }
     }
 
     pub fn into_inner(self) -> R {
-        self.reader.into_inner()
+        self.reader
     }
 
     async fn fill_buffer(&mut self) -> Result<(), ReadError> {
-        match self.reader.fill_buf().await {
-            Ok(bytes) if bytes.is_empty() => Err(ReadError::IOError(io::Error::new(
+        // Move the (partial) current message to the start of the buffer, to
+        // make room for more data.
+        if self.start > 0 {
+            self.buf.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+
+        // NOTE: The buffer only grows when a message doesn't fit into
+        // it.
+        if self.buf.len() - self.end < READ_SIZE {
+            self.buf.resize(self.end + READ_SIZE, 0);
+        }
+
+        match self.reader.read(&mut self.buf[self.end..]).await {
+            Ok(0) => Err(ReadError::IOError(io::Error::new(
                 io::ErrorKind::UnexpectedEof,
                 "Read zero bytes",
             ))),
-            Ok(bytes) => {
-                // Add the available bytes to our buffer.
-                self.buf.extend_from_slice(bytes);
-
-                // Tell the reader that we consumed the values.
-                let len = bytes.len();
-                self.reader.consume_unpin(len);
+            Ok(n) => {
+                self.end += n;
                 Ok(())
             }
             Err(err) => Err(ReadError::IOError(err)),
@@ -53,34 +78,134 @@ where
     }
 
     pub async fn recv(&mut self) -> Result<Message, ReadError> {
+        let msg = self.recv_raw().await?;
+        rmp_serde::from_slice(msg).map_err(ReadError::RmpError)
+    }
+
+    /// Receive the next message without decoding it. The returned slice
+    /// contains exactly one msgpack encoded message.
+    pub async fn recv_raw(&mut self) -> Result<&[u8], ReadError> {
+        self.start += self.consumed;
+        self.consumed = 0;
+
         loop {
-            let mut cursor = std::io::Cursor::new(&self.buf);
-
-            // Try decoding value from the buffer's current content.
-            match rmp_serde::from_read::<_, Message>(&mut cursor) {
-                Ok(val) => {
-                    // All good, there was enough data. Drop the read data.
-                    let at = cursor.position() as usize;
-                    self.buf = self.buf.split_off(at);
-
-                    return Ok(val);
-                }
-                // If we got an UnexpectedEof error, try reading more data into
-                // the buffer.
-                Err(rmp_serde::decode::Error::InvalidMarkerRead(err))
-                | Err(rmp_serde::decode::Error::InvalidDataRead(err))
-                    if err.kind() == io::ErrorKind::UnexpectedEof =>
-                {
-                    self.fill_buffer().await?
-                }
-                Err(err) => {
-                    return Err(ReadError::RmpError(err));
-                }
+            // NOTE: The scanner continues from where it left off, so
+            // each byte of the message is scanned only once, regardless of
+            // how many reads it takes to receive the whole message.
+            if let Some(len) = self.scanner.scan(&self.buf[self.start..self.end])? {
+                self.scanner = Scanner::new();
+                self.consumed = len;
+                return Ok(&self.buf[self.start..self.start + len]);
             }
+
+            self.fill_buffer().await?;
+        }
+    }
+}
+
+/// Incremental msgpack scanner, which finds the end of a msgpack value
+/// without decoding it.
+#[derive(Debug)]
+struct Scanner {
+    /// Position of the next unscanned marker, relative to the start of the
+    /// value. Might point past the end of the data received so far.
+    pos: usize,
+    /// Number of (nested) values left to scan.
+    remaining: u64,
+}
+
+impl Scanner {
+    fn new() -> Self {
+        Self {
+            pos: 0,
+            remaining: 1,
+        }
+    }
+
+    /// Continues scanning `buf`, which starts at the beginning of the value.
+    /// Returns the length of the value once all of it is in `buf`.
+    fn scan(&mut self, buf: &[u8]) -> Result<Option<usize>, ReadError> {
+        while self.remaining > 0 {
+            let rest = match buf.get(self.pos..) {
+                Some(rest) => rest,
+                None => return Ok(None),
+            };
+            let (len, children) = match marker(rest)? {
+                Some(marker) => marker,
+                None => return Ok(None),
+            };
+
+            self.pos += len;
+            self.remaining = self.remaining - 1 + children;
         }
+
+        Ok((self.pos <= buf.len()).then_some(self.pos))
     }
 }
 
+/// Reads the marker at the start of `buf`. Returns the length of the value
+/// excluding its children (i.e. array items or map entries), and the number
+/// of its children. If `buf` doesn't contain all of the marker (and the
+/// length that follows it), `None` is returned.
+fn marker(buf: &[u8]) -> Result<Option<(usize, u64)>, ReadError> {
+    // Reads a big endian length of `n` bytes following the marker.
+    let len = |n: usize| -> Option<usize> {
+        buf.get(1..1 + n)
+            .map(|bytes| bytes.iter().fold(0, |acc, b| (acc << 8) | *b as usize))
+    };
+
+    let marker = match buf.first() {
+        Some(marker) => *marker,
+        None => return Ok(None),
+    };
+
+    Ok(match marker {
+        // positive fixint, nil, false, true, negative fixint
+        0x00..=0x7f | 0xc0 | 0xc2 | 0xc3 | 0xe0..=0xff => Some((1, 0)),
+        // fixmap
+        0x80..=0x8f => Some((1, 2 * (marker & 0x0f) as u64)),
+        // fixarray
+        0x90..=0x9f => Some((1, (marker & 0x0f) as u64)),
+        // fixstr
+        0xa0..=0xbf => Some((1 + (marker & 0x1f) as usize, 0)),
+        // bin 8/16/32, str 8/16/32
+        0xc4 | 0xd9 => len(1).map(|n| (2 + n, 0)),
+        0xc5 | 0xda => len(2).map(|n| (3 + n, 0)),
+        0xc6 | 0xdb => len(4).map(|n| (5 + n, 0)),
+        // ext 8/16/32
+        0xc7 => len(1).map(|n| (3 + n, 0)),
+        0xc8 => len(2).map(|n| (4 + n, 0)),
+        0xc9 => len(4).map(|n| (6 + n, 0)),
+        // float 32/64
+        0xca => Some((5, 0)),
+        0xcb => Some((9, 0)),
+        // uint/int 8/16/32/64
+        0xcc | 0xd0 => Some((2, 0)),
+        0xcd | 0xd1 => Some((3, 0)),
+        0xce | 0xd2 => Some((5, 0)),
+        0xcf | 0xd3 => Some((9, 0)),
+        // fixext 1/2/4/8/16
+        0xd4 => Some((3, 0)),
+        0xd5 => Some((4, 0)),
+        0xd6 => Some((6, 0)),
+        0xd7 => Some((10, 0)),
+        0xd8 => Some((18, 0)),
+        // array 16/32
+        0xdc => len(2).map(|n| (3, n as u64)),
+        0xdd => len(4).map(|n| (5, n as u64)),
+        // map 16/32
+        0xde => len(2).map(|n| (3, 2 * n as u64)),
+        0xdf => len(4).map(|n| (5, 2 * n as u64)),
+        // NOTE: 0xc1 is never used.
+        0xc1 => {
+            return Err(ReadError::IOError(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid msgpack marker 0xc1",
+            )))
+        }
+    })
+}
+
 impl<R> From<R> for RpcReader<R>
 where
     R: AsyncRead + Unpin,
@@ -0,0 +1,134 @@
+//! Recording and replaying of the rpc traffic.
+//!
+//! A recording is a sequence of msgpack encoded `Entry`s. Each entry is a
+//! `[time, direction, message]` array, where the message is exactly the
+//! message that went over the wire.
+
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::Message;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Direction {
+    /// Message received from neovim.
+    Incoming,
+    /// Message sent to neovim.
+    Outgoing,
+}
+
+/// A single recorded message.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Entry {
+    /// Time since the start of the recording, in microseconds.
+    pub time: u64,
+    pub direction: Direction,
+    pub message: Message,
+}
+
+impl Entry {
+    /// Time since the start of the recording.
+    pub fn time(&self) -> Duration {
+        Duration::from_micros(self.time)
+    }
+}
+
+/// Writes the recorded messages to the underlying writer. The recorder is
+/// cheap to clone, and all the clones write to the same recording.
+///
+/// Recording stops at the first write error, see `Recorder::take_error`.
+#[derive(Clone)]
+pub struct Recorder {
+    inner: Arc<Mutex<Inner>>,
+}
+
+struct Inner {
+    writer: Option<Box<dyn io::Write + Send>>,
+    start: Instant,
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    pub fn new<W: io::Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                writer: Some(Box::new(writer)),
+                start: Instant::now(),
+                error: None,
+            })),
+        }
+    }
+
+    fn inner(&self) -> MutexGuard<'_, Inner> {
+        // NOTE: The lock is never held while calling into user code, so
+        // it can't be poisoned.
+        self.inner.lock().expect("recorder lock poisoned")
+    }
+
+    /// Records a single msgpack encoded message.
+    pub fn record(&self, direction: Direction, msg: &[u8]) {
+        let mut inner = self.inner();
+        let time = inner.start.elapsed().as_micros() as u64;
+
+        let writer = match inner.writer.as_mut() {
+            Some(writer) => writer,
+            None => return,
+        };
+
+        // NOTE: The message is already encoded, so the entry is
+        // encoded piece by piece instead of serializing an `Entry`.
+        let mut header = Vec::with_capacity(16);
+        rmp::encode::write_array_len(&mut header, 3).expect("write to vec");
+        rmp_serde::encode::write(&mut header, &time).expect("write to vec");
+        rmp_serde::encode::write(&mut header, &direction).expect("write to vec");
+
+        let res = writer
+            .write_all(&header)
+            .and_then(|_| writer.write_all(msg))
+            .and_then(|_| writer.flush());
+
+        if let Err(err) = res {
+            inner.writer = None;
+            inner.error = Some(err);
+        }
+    }
+
+    /// Returns the error that stopped the recording, if any.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.inner().error.take()
+    }
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
+
+/// Reads the entries of a recording.
+pub struct Replay<R> {
+    reader: R,
+}
+
+impl<R: io::Read> Replay<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: io::Read> Iterator for Replay<R> {
+    type Item = Result<Entry, rmp_serde::decode::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match rmp_serde::from_read(&mut self.reader) {
+            // The end of the recording.
+            Err(rmp_serde::decode::Error::InvalidMarkerRead(err))
+                if err.kind() == io::ErrorKind::UnexpectedEof =>
+            {
+                None
+            }
+            res => Some(res),
+        }
+    }
+}
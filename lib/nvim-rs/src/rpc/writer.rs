@@ -6,6 +6,28 @@ use futures::prelude::*;
 pub enum WriteError {
     RmpSerde(rmp_serde::encode::Error),
     IO(io::Error),
+    /// The writer is closed (e.g. the client's writer task is gone).
+    Closed,
+}
+
+impl std::fmt::Display for WriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RmpSerde(err) => write!(f, "failed to encode message: {}", err),
+            Self::IO(err) => write!(f, "failed to write message: {}", err),
+            Self::Closed => write!(f, "writer closed"),
+        }
+    }
+}
+
+impl std::error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::RmpSerde(err) => Some(err),
+            Self::IO(err) => Some(err),
+            Self::Closed => None,
+        }
+    }
 }
 
 impl From<rmp_serde::encode::Error> for WriteError {
@@ -75,13 +97,28 @@ where
     }
 }
 
+/// Encode a rpc message to a buffer which can then be written to a writer.
+pub(crate) fn encode_rpc<D: serde::Serialize>(data: &D) -> Result<Vec<u8>, WriteError> {
+    let mut buf = Vec::new();
+    rmp_serde::encode::write_named(&mut buf, data)?;
+
+    Ok(buf)
+}
+
+/// Encodes `args` into a value. The args are encoded through msgpack (and not
+/// with `rmpv::ext::to_value`) so that structs end up as maps, like they do
+/// when the args are written directly.
+pub(crate) fn to_value<V: serde::Serialize>(args: &V) -> Result<rmpv::Value, WriteError> {
+    let buf = encode_rpc(args)?;
+    rmpv::decode::read_value(&mut buf.as_slice())
+        .map_err(|err| WriteError::IO(io::Error::new(io::ErrorKind::InvalidData, err)))
+}
+
 async fn write_rpc<W: AsyncWrite + Unpin, D: serde::Serialize>(
     w: &mut W,
     data: &D,
 ) -> Result<(), WriteError> {
-    let mut buf = Vec::new();
-    // Encode the message to a buffer which we can the write to the writer.
-    rmp_serde::encode::write_named(&mut buf, data)?;
+    let buf = encode_rpc(data)?;
 
     w.write_all(&buf).await?;
     w.flush().await?;
@@ -0,0 +1,384 @@
+//! Headless model of neovim's screen. `Screen` applies the (linegrid and
+//! multigrid) ui events to in-memory grids, so the screen's contents can be
+//! inspected without a ui toolkit, e.g. in tests.
+//!
+//! ```ignore
+//! let mut screen = Screen::default();
+//! while let Some(redraw) = redraw.next().await {
+//!     screen.apply_all(redraw.events()?);
+//! }
+//! assert_eq!(screen.grid(1).unwrap().row_text(0), "hello");
+//! ```
+
+use std::collections::HashMap;
+
+use crate::types::uievents::{GridLine, GridScroll, WinFloatPos, WinPos};
+use crate::types::{HlAttr, UiEvent, Window};
+
+/// A single cell of a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    /// The cell's text. Empty for the cell right of a double width cell.
+    pub text: String,
+    /// Highlight id of the cell, see `Screen::hl_attr`. Zero is the default
+    /// highlight.
+    pub hl_id: i64,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            text: String::from(" "),
+            hl_id: 0,
+        }
+    }
+}
+
+/// Where a window's grid is placed on the screen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Placement {
+    /// A normal window, positioned on the default grid.
+    Normal {
+        startrow: i64,
+        startcol: i64,
+        width: i64,
+        height: i64,
+    },
+    /// A floating window, anchored to `anchor_grid`.
+    Float {
+        anchor: String,
+        anchor_grid: i64,
+        anchor_row: f64,
+        anchor_col: f64,
+        focusable: bool,
+        zindex: i64,
+    },
+    /// A window displayed outside of the screen.
+    External,
+    /// The message grid, at `row` of the default grid.
+    Message { row: i64, scrolled: bool },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Grid {
+    rows: Vec<Vec<Cell>>,
+    /// The window displayed on the grid, if any (e.g. the default grid and
+    /// the message grid have no windows).
+    pub win: Option<Window>,
+    pub placement: Option<Placement>,
+    /// Set by `win_hide`, and unset once the grid is positioned again.
+    pub hidden: bool,
+}
+
+impl Grid {
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> &[Vec<Cell>] {
+        &self.rows
+    }
+
+    pub fn cell(&self, row: usize, col: usize) -> Option<&Cell> {
+        self.rows.get(row)?.get(col)
+    }
+
+    /// Text of the row, with the trailing whitespace included.
+    pub fn row_text(&self, row: usize) -> String {
+        self.rows
+            .get(row)
+            .map(|cells| cells.iter().map(|cell| cell.text.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Highlight ids of the row's cells.
+    pub fn row_hl_ids(&self, row: usize) -> Vec<i64> {
+        self.rows
+            .get(row)
+            .map(|cells| cells.iter().map(|cell| cell.hl_id).collect())
+            .unwrap_or_default()
+    }
+
+    /// Text of the whole grid, rows separated by newlines and trailing
+    /// whitespace trimmed from each row.
+    pub fn text(&self) -> String {
+        (0..self.height())
+            .map(|row| self.row_text(row).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        self.rows.resize_with(height, Vec::new);
+        for row in self.rows.iter_mut() {
+            row.resize(width, Cell::default());
+        }
+    }
+
+    fn clear(&mut self) {
+        for cell in self.rows.iter_mut().flatten() {
+            *cell = Cell::default();
+        }
+    }
+
+    fn line(&mut self, event: GridLine) {
+        let row = match self.rows.get_mut(event.row as usize) {
+            Some(row) => row,
+            None => return,
+        };
+
+        // NOTE: Cells without a hl id use the previous cell's id. The
+        // first cell always has one.
+        let mut hl_id = 0;
+        let mut cells = row.iter_mut().skip(event.col_start as usize);
+        for data in event.data {
+            if let Some(id) = data.hl_id {
+                hl_id = id;
+            }
+
+            for _ in 0..data.repeat.unwrap_or(1) {
+                match cells.next() {
+                    Some(cell) => {
+                        cell.text = data.text.to_string();
+                        cell.hl_id = hl_id;
+                    }
+                    None => return,
+                }
+            }
+        }
+    }
+
+    fn scroll(&mut self, event: &GridScroll) {
+        let top = event.top as usize;
+        let bot = (event.bot as usize).min(self.height());
+        let left = event.left as usize;
+        let right = (event.right as usize).min(self.width());
+        if top >= bot || left >= right {
+            return;
+        }
+
+        // NOTE: The rows the region is scrolled away from keep their
+        // contents, neovim redraws them with `grid_line`.
+        let move_row = |rows: &mut Vec<Vec<Cell>>, src: usize, dst: usize| {
+            let cells = rows[src][left..right].to_vec();
+            rows[dst][left..right].clone_from_slice(&cells);
+        };
+
+        let count = event.rows.unsigned_abs() as usize;
+        if event.rows > 0 {
+            for dst in top..bot.saturating_sub(count) {
+                move_row(&mut self.rows, dst + count, dst);
+            }
+        } else {
+            for dst in (top + count..bot).rev() {
+                move_row(&mut self.rows, dst - count, dst);
+            }
+        }
+    }
+}
+
+/// Position of the cursor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cursor {
+    pub grid: i64,
+    pub row: i64,
+    pub col: i64,
+}
+
+/// Default colors of the screen, set by `default_colors_set`. The colors are
+/// -1 if not set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultColors {
+    pub fg: i64,
+    pub bg: i64,
+    pub sp: i64,
+}
+
+impl Default for DefaultColors {
+    fn default() -> Self {
+        Self {
+            fg: -1,
+            bg: -1,
+            sp: -1,
+        }
+    }
+}
+
+/// Screen state built from ui events. See the module docs.
+#[derive(Debug, Default)]
+pub struct Screen {
+    grids: HashMap<i64, Grid>,
+    hl_attrs: HashMap<i64, HlAttr>,
+    default_colors: DefaultColors,
+    cursor: Cursor,
+}
+
+impl Screen {
+    /// Grid with the given id. The default grid is 1.
+    pub fn grid(&self, id: i64) -> Option<&Grid> {
+        self.grids.get(&id)
+    }
+
+    pub fn grids(&self) -> impl Iterator<Item = (i64, &Grid)> {
+        self.grids.iter().map(|(id, grid)| (*id, grid))
+    }
+
+    /// The grid displaying `win`.
+    pub fn window_grid(&self, win: Window) -> Option<(i64, &Grid)> {
+        self.grids().find(|(_, grid)| grid.win == Some(win))
+    }
+
+    /// The rgb attributes of a highlight id.
+    pub fn hl_attr(&self, id: i64) -> Option<&HlAttr> {
+        self.hl_attrs.get(&id)
+    }
+
+    pub fn default_colors(&self) -> DefaultColors {
+        self.default_colors
+    }
+
+    pub fn cursor(&self) -> Cursor {
+        self.cursor
+    }
+
+    pub fn apply_all<'a, I>(&mut self, events: I)
+    where
+        I: IntoIterator<Item = UiEvent<'a>>,
+    {
+        for event in events {
+            self.apply(event);
+        }
+    }
+
+    /// Applies a single ui event. Events that don't affect the grids are
+    /// ignored, as are events for grids that don't exist.
+    pub fn apply(&mut self, event: UiEvent<'_>) {
+        match event {
+            UiEvent::GridResize(events) => events.into_iter().for_each(|event| {
+                self.grids
+                    .entry(event.grid)
+                    .or_default()
+                    .resize(event.width as usize, event.height as usize)
+            }),
+            UiEvent::GridClear(events) => events.into_iter().for_each(|event| {
+                if let Some(grid) = self.grids.get_mut(&event.grid) {
+                    grid.clear();
+                }
+            }),
+            UiEvent::GridLine(events) => events.into_iter().for_each(|event| {
+                if let Some(grid) = self.grids.get_mut(&event.grid) {
+                    grid.line(event);
+                }
+            }),
+            UiEvent::GridScroll(events) => events.into_iter().for_each(|event| {
+                if let Some(grid) = self.grids.get_mut(&event.grid) {
+                    grid.scroll(&event);
+                }
+            }),
+            UiEvent::GridCursorGoto(events) => events.into_iter().for_each(|event| {
+                self.cursor = Cursor {
+                    grid: event.grid,
+                    row: event.row,
+                    col: event.col,
+                };
+            }),
+            UiEvent::GridDestroy(events) => events.into_iter().for_each(|event| {
+                self.grids.remove(&event.grid);
+            }),
+            UiEvent::HlAttrDefine(events) => events.into_iter().for_each(|event| {
+                self.hl_attrs.insert(event.id, event.rgb_attrs);
+            }),
+            UiEvent::DefaultColorsSet(events) => events.into_iter().for_each(|event| {
+                self.default_colors = DefaultColors {
+                    fg: event.rgb_fg,
+                    bg: event.rgb_bg,
+                    sp: event.rgb_sp,
+                };
+            }),
+            UiEvent::WinPos(events) => events.into_iter().for_each(|event| {
+                let WinPos {
+                    grid,
+                    win,
+                    startrow,
+                    startcol,
+                    width,
+                    height,
+                } = event;
+                self.place(
+                    grid,
+                    Some(win),
+                    Placement::Normal {
+                        startrow,
+                        startcol,
+                        width,
+                        height,
+                    },
+                );
+            }),
+            UiEvent::WinFloatPos(events) => events.into_iter().for_each(|event| {
+                let WinFloatPos {
+                    grid,
+                    win,
+                    anchor,
+                    anchor_grid,
+                    anchor_row,
+                    anchor_col,
+                    focusable,
+                    zindex,
+                } = event;
+                self.place(
+                    grid,
+                    Some(win),
+                    Placement::Float {
+                        anchor,
+                        anchor_grid,
+                        anchor_row,
+                        anchor_col,
+                        focusable,
+                        zindex,
+                    },
+                );
+            }),
+            UiEvent::WinExternalPos(events) => events.into_iter().for_each(|event| {
+                self.place(event.grid, Some(event.win), Placement::External);
+            }),
+            UiEvent::MsgSetPos(events) => events.into_iter().for_each(|event| {
+                self.place(
+                    event.grid,
+                    None,
+                    Placement::Message {
+                        row: event.row,
+                        scrolled: event.scrolled,
+                    },
+                );
+            }),
+            UiEvent::WinHide(events) => events.into_iter().for_each(|event| {
+                if let Some(grid) = self.grids.get_mut(&event.grid) {
+                    grid.hidden = true;
+                }
+            }),
+            UiEvent::WinClose(events) => events.into_iter().for_each(|event| {
+                if let Some(grid) = self.grids.get_mut(&event.grid) {
+                    grid.win = None;
+                    grid.placement = None;
+                    grid.hidden = false;
+                }
+            }),
+            _ => {}
+        }
+    }
+
+    fn place(&mut self, grid: i64, win: Option<Window>, placement: Placement) {
+        // NOTE: The grid is usually resized before it's positioned,
+        // but not necessarily (e.g. when the grid is reused for another
+        // window).
+        let grid = self.grids.entry(grid).or_default();
+        grid.win = win;
+        grid.placement = Some(placement);
+        grid.hidden = false;
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use futures::channel::mpsc;
+use futures::prelude::*;
+
+use crate::handler::error_value;
+use crate::rpc::{
+    message::{Notification, Request},
+    record::{Direction, Recorder},
+    Message, ReadError, RpcReader,
+};
+use crate::types::Redraw;
+use crate::Client;
+
+/// Message from neovim, other than a response. See `Session::incoming`.
+#[derive(Debug)]
+pub enum Incoming {
+    Redraw(Redraw),
+    Notification(Notification),
+    Request(Request),
+}
+
+/// Drives the reading side of the rpc connection. Responses are resolved
+/// through the client, and everything else is dispatched to the subscribed
+/// streams.
+///
+/// Subscribe to the streams before calling `Session::run`.
+pub struct Session<R>
+where
+    R: AsyncRead + Unpin,
+{
+    reader: RpcReader<R>,
+    client: Client,
+
+    redraw: Option<mpsc::UnboundedSender<Redraw>>,
+    requests: Option<mpsc::UnboundedSender<Request>>,
+    notifications: HashMap<String, mpsc::UnboundedSender<rmpv::Value>>,
+    incoming: Option<mpsc::UnboundedSender<Incoming>>,
+    recorder: Option<Recorder>,
+}
+
+impl<R> Session<R>
+where
+    R: AsyncRead + Unpin,
+{
+    pub fn new(reader: R, client: Client) -> Self {
+        Self {
+            reader: RpcReader::new(reader),
+            client,
+            redraw: None,
+            requests: None,
+            notifications: HashMap::new(),
+            incoming: None,
+            recorder: None,
+        }
+    }
+
+    pub fn client(&self) -> Client {
+        self.client.clone()
+    }
+
+    /// Stream of the `redraw` notifications. The notifications are passed on
+    /// without decoding them, but each one is copied out of the read buffer
+    /// (see `Redraw`).
+    pub fn redraw(&mut self) -> mpsc::UnboundedReceiver<Redraw> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.redraw = Some(sender);
+        receiver
+    }
+
+    /// Stream of the params of notifications with the given method.
+    pub fn notifications<S: Into<String>>(
+        &mut self,
+        method: S,
+    ) -> mpsc::UnboundedReceiver<rmpv::Value> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.notifications.insert(method.into(), sender);
+        receiver
+    }
+
+    /// Stream of incoming requests. The subscriber is responsible for
+    /// responding to the requests (see `Client::respond`). Without a
+    /// subscriber, requests are responded with an error.
+    pub fn requests(&mut self) -> mpsc::UnboundedReceiver<Request> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.requests = Some(sender);
+        receiver
+    }
+
+    /// Stream of the messages that have no other subscriber, in the order
+    /// they're received. Unlike with separate streams, the order between
+    /// the different kinds of messages is kept.
+    pub fn incoming(&mut self) -> mpsc::UnboundedReceiver<Incoming> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.incoming = Some(sender);
+        receiver
+    }
+
+    /// Record all the incoming messages to `recorder`. See also
+    /// `Client::record`.
+    pub fn record(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Read and dispatch messages until reading fails (e.g. the other end
+    /// closes the connection).
+    pub async fn run(mut self) -> Result<(), ReadError> {
+        loop {
+            let msg = self.reader.recv_raw().await?;
+
+            if let Some(recorder) = self.recorder.as_ref() {
+                recorder.record(Direction::Incoming, msg);
+            }
+
+            if self.redraw.is_some() || self.incoming.is_some() {
+                if let Some(redraw) = Redraw::from_message(msg) {
+                    self.dispatch_redraw(redraw);
+                    continue;
+                }
+            }
+
+            match rmp_serde::from_slice(msg).map_err(ReadError::RmpError)? {
+                // NOTE: If the caller is gone, there is no one to
+                // receive the response.
+                Message::Response(response) => {
+                    let _ = self.client.handle_response(response);
+                }
+                Message::Request(request) => self.dispatch_request(request).await,
+                Message::Notification(notification) => self.dispatch_notification(notification),
+            }
+        }
+    }
+
+    fn dispatch_redraw(&mut self, redraw: Redraw) {
+        if let Err(redraw) = send(&mut self.redraw, redraw) {
+            let _ = send(&mut self.incoming, Incoming::Redraw(redraw));
+        }
+    }
+
+    async fn dispatch_request(&mut self, request: Request) {
+        let request = match send(&mut self.requests, request) {
+            Ok(()) => return,
+            Err(request) => request,
+        };
+
+        if let Err(Incoming::Request(request)) =
+            send(&mut self.incoming, Incoming::Request(request))
+        {
+            let res = Err(error_value(format!(
+                "unhandled request method: {}",
+                request.method
+            )));
+            // NOTE: If writing fails, so will everything else.
+            let _ = self.client.respond(request.msgid, res).await;
+        }
+    }
+
+    fn dispatch_notification(&mut self, notification: Notification) {
+        let Notification { method, params, .. } = notification;
+
+        let params = match self.notifications.get(&method) {
+            Some(sender) => match sender.unbounded_send(params) {
+                Ok(()) => return,
+                Err(err) => {
+                    // The subscriber is gone.
+                    self.notifications.remove(&method);
+                    err.into_inner()
+                }
+            },
+            None => params,
+        };
+
+        let notification = Notification::new(method, params);
+        let _ = send(&mut self.incoming, Incoming::Notification(notification));
+    }
+}
+
+/// Sends `msg` to the subscriber, unsubscribing it if it's gone. If the
+/// message wasn't sent, it's returned back.
+fn send<T>(sender: &mut Option<mpsc::UnboundedSender<T>>, msg: T) -> Result<(), T> {
+    let res = match sender {
+        Some(sender) => sender.unbounded_send(msg),
+        None => return Err(msg),
+    };
+
+    res.map_err(|err| {
+        *sender = None;
+        err.into_inner()
+    })
+}
@@ -0,0 +1,299 @@
+//! Scriptable stand-in for neovim, for testing without neovim installed.
+//!
+//! ```ignore
+//! let mut server = MockServer::new(io);
+//! server
+//!     .expect(Expect::request("nvim_input").params(args!["i"]).returns(1))
+//!     .redraw(vec![ui_event("grid_clear", [(1,)]), ui_event("flush", [()])]);
+//! server.run().await.unwrap();
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+use futures::io::{ReadHalf, WriteHalf};
+use futures::prelude::*;
+
+use crate::handler::error_value;
+use crate::rpc::writer::{encode_rpc, to_value};
+use crate::rpc::{Message, ReadError, RpcReader, WriteError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Request,
+    Notification,
+}
+
+/// An expected message from the client, and the reply to it.
+#[derive(Debug)]
+pub struct Expect {
+    kind: Kind,
+    method: String,
+    /// Expected params. If not set, any params are accepted.
+    params: Option<rmpv::Value>,
+    reply: Result<rmpv::Value, rmpv::Value>,
+}
+
+impl Expect {
+    /// Expect a request. By default, the request is responded with nil.
+    pub fn request<S: Into<String>>(method: S) -> Self {
+        Self {
+            kind: Kind::Request,
+            method: method.into(),
+            params: None,
+            reply: Ok(rmpv::Value::Nil),
+        }
+    }
+
+    /// Expect a notification.
+    pub fn notification<S: Into<String>>(method: S) -> Self {
+        Self {
+            kind: Kind::Notification,
+            ..Self::request(method)
+        }
+    }
+
+    /// Expect the message to have exactly these params.
+    pub fn params<V: serde::Serialize>(mut self, params: V) -> Self {
+        self.params = Some(to_value(&params).expect("failed to encode params"));
+        self
+    }
+
+    /// Respond to the request with `result`.
+    pub fn returns<V: serde::Serialize>(mut self, result: V) -> Self {
+        self.reply = Ok(to_value(&result).expect("failed to encode result"));
+        self
+    }
+
+    /// Respond to the request with an (exception) error.
+    pub fn fails<S: Into<String>>(self, message: S) -> Self {
+        self.error(error_value(message))
+    }
+
+    /// Respond to the request with an arbitrary error value.
+    pub fn error(mut self, error: rmpv::Value) -> Self {
+        self.reply = Err(error);
+        self
+    }
+
+    fn matches(&self, kind: Kind, method: &str, params: &rmpv::Value) -> bool {
+        self.kind == kind && self.method == method && self.params.iter().all(|p| p == params)
+    }
+}
+
+impl Display for Expect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            Kind::Request => write!(f, "request {:?}", self.method)?,
+            Kind::Notification => write!(f, "notification {:?}", self.method)?,
+        }
+
+        match self.params {
+            Some(ref params) => write!(f, " with params {}", params),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Step {
+    Expect(Expect),
+    Notify { method: String, params: rmpv::Value },
+}
+
+#[derive(Debug)]
+pub enum MockError {
+    Read(ReadError),
+    Write(WriteError),
+    /// The client sent something that wasn't in the script. `expected` is
+    /// the expectation that was next in the script, if any.
+    Unexpected {
+        expected: Option<String>,
+        got: Message,
+    },
+    /// The client closed the connection before the script was done.
+    Closed {
+        remaining: usize,
+    },
+}
+
+impl Display for MockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(err) => write!(f, "failed to read message: {:?}", err),
+            Self::Write(err) => write!(f, "{}", err),
+            Self::Unexpected {
+                expected: Some(expected),
+                got,
+            } => write!(f, "expected {}, got {:?}", expected, got),
+            Self::Unexpected {
+                expected: None,
+                got,
+            } => write!(f, "unexpected message: {:?}", got),
+            Self::Closed { remaining } => write!(
+                f,
+                "connection closed with {} steps left in the script",
+                remaining
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MockError {}
+
+impl From<WriteError> for MockError {
+    fn from(err: WriteError) -> Self {
+        Self::Write(err)
+    }
+}
+
+/// Msgpack-rpc server that plays a script: it expects the client to make
+/// the scripted calls in order, responds to them with canned responses, and
+/// sends the scripted notifications (e.g. `redraw`) to the client.
+pub struct MockServer<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    reader: RpcReader<ReadHalf<S>>,
+    writer: WriteHalf<S>,
+    script: VecDeque<Step>,
+}
+
+impl<S> MockServer<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    pub fn new(io: S) -> Self {
+        let (reader, writer) = io.split();
+        Self {
+            reader: RpcReader::new(reader),
+            writer,
+            script: VecDeque::new(),
+        }
+    }
+
+    /// Add an expected message to the script.
+    pub fn expect(&mut self, expect: Expect) -> &mut Self {
+        self.script.push_back(Step::Expect(expect));
+        self
+    }
+
+    /// Add a notification to the script.
+    pub fn notify<M, V>(&mut self, method: M, params: V) -> &mut Self
+    where
+        M: Into<String>,
+        V: serde::Serialize,
+    {
+        self.script.push_back(Step::Notify {
+            method: method.into(),
+            params: to_value(&params).expect("failed to encode params"),
+        });
+        self
+    }
+
+    /// Add a `redraw` notification to the script. See `ui_event`.
+    pub fn redraw(&mut self, events: Vec<rmpv::Value>) -> &mut Self {
+        self.notify("redraw", events)
+    }
+
+    /// Play the script until its end.
+    pub async fn run_script(&mut self) -> Result<(), MockError> {
+        while let Some(step) = self.script.pop_front() {
+            match step {
+                Step::Expect(expect) => self.handle_expect(expect).await?,
+                Step::Notify { method, params } => self.write(&(2, method, params)).await?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Play the script, close the server's end of the connection, and then
+    /// make sure the client doesn't send anything else before closing its
+    /// end.
+    pub async fn run(mut self) -> Result<(), MockError> {
+        self.run_script().await?;
+
+        // NOTE: The session keeps a client around, so the client end
+        // won't close before the session sees the server end close.
+        self.writer.close().await.map_err(WriteError::from)?;
+
+        match self.reader.recv().await {
+            Ok(got) => Err(MockError::Unexpected {
+                expected: None,
+                got,
+            }),
+            Err(ReadError::IOError(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                Ok(())
+            }
+            Err(err) => Err(MockError::Read(err)),
+        }
+    }
+
+    // NOTE: Not using `RpcWriter`, so that the server can be spawned
+    // on multi threaded executors.
+    async fn write<D: serde::Serialize>(&mut self, msg: &D) -> Result<(), MockError> {
+        let buf = encode_rpc(msg)?;
+        self.writer
+            .write_all(&buf)
+            .await
+            .map_err(WriteError::from)?;
+        self.writer.flush().await.map_err(WriteError::from)?;
+        Ok(())
+    }
+
+    async fn handle_expect(&mut self, expect: Expect) -> Result<(), MockError> {
+        let got = match self.reader.recv().await {
+            Ok(got) => got,
+            Err(ReadError::IOError(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                return Err(MockError::Closed {
+                    remaining: self.script.len() + 1,
+                })
+            }
+            Err(err) => return Err(MockError::Read(err)),
+        };
+
+        let matches = match got {
+            Message::Request(ref req) => expect.matches(Kind::Request, &req.method, &req.params),
+            Message::Notification(ref n) => {
+                expect.matches(Kind::Notification, &n.method, &n.params)
+            }
+            Message::Response(_) => false,
+        };
+
+        if !matches {
+            return Err(MockError::Unexpected {
+                expected: Some(expect.to_string()),
+                got,
+            });
+        }
+
+        if let Message::Request(req) = got {
+            match expect.reply {
+                Ok(result) => self.write(&(1, req.msgid, (), result)).await?,
+                Err(error) => self.write(&(1, req.msgid, error, ())).await?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Encodes a single ui event for `MockServer::redraw`. Each item of `calls`
+/// is the args of one call of the event (e.g. a tuple, or `()` for no args).
+pub fn ui_event<V, I>(name: &str, calls: I) -> rmpv::Value
+where
+    V: serde::Serialize,
+    I: IntoIterator<Item = V>,
+{
+    rmpv::Value::Array(
+        std::iter::once(rmpv::Value::from(name))
+            .chain(calls.into_iter().map(|args| {
+                match to_value(&args).expect("failed to encode ui event") {
+                    rmpv::Value::Nil => rmpv::Value::Array(vec![]),
+                    args => args,
+                }
+            }))
+            .collect(),
+    )
+}
@@ -0,0 +1,30 @@
+//! Ready made connections to neovim for the tokio (`tokio` feature) and
+//! async-std (`async-std` feature) runtimes. Each of the runtime modules can
+//! spawn `nvim --embed`, connect to a neovim server, and talk to neovim over
+//! stdio (e.g. when started by neovim's `jobstart(..., {'rpc': v:true})`).
+//!
+//! The connections are returned as a client, and a session that needs to be
+//! run (see `Session::run`) for the client to get its responses. The
+//! client's writer task is spawned on the runtime.
+//!
+//! ```ignore
+//! let (client, session) = nvim_rs::transport::tokio::connect("/tmp/nvim.sock").await?;
+//! tokio::spawn(session.run());
+//! ```
+
+#[cfg(feature = "async-std")]
+pub mod async_std;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+/// Addresses in the form of `host:port` are tcp addresses, everything else
+/// is a path to a unix socket.
+pub fn is_tcp_address(address: &str) -> bool {
+    match address.rsplit_once(':') {
+        Some((host, port)) => {
+            !host.is_empty() && !host.contains('/') && port.parse::<u16>().is_ok()
+        }
+        None => false,
+    }
+}
+
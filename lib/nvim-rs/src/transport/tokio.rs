@@ -0,0 +1,78 @@
+//! Connections for the tokio runtime. Needs to be used within a tokio
+//! runtime, which the client's writer task is spawned to.
+
+use std::io;
+
+use futures::{AsyncRead, AsyncWrite};
+use tokio::process::{Child, Command};
+use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+
+use crate::{Client, Session};
+
+/// Reading half of the connection to neovim.
+pub type Reader = Box<dyn AsyncRead + Send + Unpin>;
+/// Writing half of the connection to neovim.
+pub type Writer = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Creates a client writing to `writer`, and a session reading `reader`.
+pub fn open(reader: Reader, writer: Writer) -> (Client, Session<Reader>) {
+    let (client, writer_task) = Client::new(writer);
+    tokio::spawn(writer_task);
+
+    let session = Session::new(reader, client.clone());
+    (client, session)
+}
+
+/// Spawns neovim with `cmd` (e.g. `Command::new("nvim")`), adding `--embed`
+/// to its args, and talks to it over its stdin and stdout. The process is
+/// killed when the returned child is dropped.
+pub fn embed(mut cmd: Command) -> io::Result<(Client, Session<Reader>, Child)> {
+    let mut child = cmd
+        .arg("--embed")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let stdin = child.stdin.take().expect("stdin not piped");
+    let stdout = child.stdout.take().expect("stdout not piped");
+
+    let (client, session) = open(Box::new(stdout.compat()), Box::new(stdin.compat_write()));
+    Ok((client, session, child))
+}
+
+/// Connects to a neovim server listening on `address` (see `:help --listen`
+/// and `super::is_tcp_address`).
+pub async fn connect(address: &str) -> io::Result<(Client, Session<Reader>)> {
+    let (reader, writer): (Reader, Writer) = if super::is_tcp_address(address) {
+        let (reader, writer) = tokio::net::TcpStream::connect(address).await?.into_split();
+        (Box::new(reader.compat()), Box::new(writer.compat_write()))
+    } else {
+        connect_unix(address).await?
+    };
+
+    Ok(open(reader, writer))
+}
+
+#[cfg(unix)]
+async fn connect_unix(path: &str) -> io::Result<(Reader, Writer)> {
+    let (reader, writer) = tokio::net::UnixStream::connect(path).await?.into_split();
+    Ok((Box::new(reader.compat()), Box::new(writer.compat_write())))
+}
+
+#[cfg(not(unix))]
+async fn connect_unix(_path: &str) -> io::Result<(Reader, Writer)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "unix sockets not supported on this platform",
+    ))
+}
+
+/// Talks to neovim over our stdin and stdout, for when neovim started us.
+/// Nothing else should use stdout while the connection is open.
+pub fn stdio() -> (Client, Session<Reader>) {
+    open(
+        Box::new(tokio::io::stdin().compat()),
+        Box::new(tokio::io::stdout().compat_write()),
+    )
+}
@@ -0,0 +1,87 @@
+//! Notifications sent for buffers attached with `nvim_buf_attach`.
+
+use serde::Deserialize;
+
+use super::manual::{impl_seq_deserialize, skip_remaining};
+use super::Buffer;
+
+pub const LINES_EVENT: &str = "nvim_buf_lines_event";
+pub const CHANGEDTICK_EVENT: &str = "nvim_buf_changedtick_event";
+pub const DETACH_EVENT: &str = "nvim_buf_detach_event";
+
+#[derive(Debug)]
+pub enum BufEvent {
+    Lines(BufLines),
+    ChangedTick(BufChangedTick),
+    Detach(BufDetach),
+}
+
+impl BufEvent {
+    /// Methods of the buffer event notifications.
+    pub const METHODS: [&'static str; 3] = [LINES_EVENT, CHANGEDTICK_EVENT, DETACH_EVENT];
+
+    /// The buffer the event is for.
+    pub fn buffer(&self) -> Buffer {
+        match self {
+            Self::Lines(event) => event.buf,
+            Self::ChangedTick(event) => event.buf,
+            Self::Detach(event) => event.buf,
+        }
+    }
+}
+
+/// Lines `firstline..lastline` (zero indexed, end exclusive) were replaced
+/// with `linedata`.
+#[derive(Debug)]
+pub struct BufLines {
+    pub buf: Buffer,
+    /// Not set if the buffer's changedtick didn't change (e.g. for the
+    /// initial lines sent with `send_buffer`).
+    pub changedtick: Option<i64>,
+    pub firstline: i64,
+    /// End of the replaced lines, or -1 for the initial lines.
+    pub lastline: i64,
+    pub linedata: Vec<String>,
+    /// Set if the change was split into multiple events, and this is not the
+    /// last one of them.
+    pub more: bool,
+}
+
+impl_seq_deserialize!(BufLines {
+    buf,
+    changedtick,
+    firstline,
+    lastline,
+    linedata,
+    more
+});
+
+#[derive(Debug)]
+pub struct BufChangedTick {
+    pub buf: Buffer,
+    pub changedtick: i64,
+}
+
+impl_seq_deserialize!(BufChangedTick { buf, changedtick });
+
+/// The buffer was detached, e.g. because it was unloaded.
+#[derive(Debug)]
+pub struct BufDetach {
+    pub buf: Buffer,
+}
+
+impl_seq_deserialize!(BufDetach { buf });
+
+/// Decodes the params of a buffer event notification (see
+/// `BufEvent::METHODS`). Returns `None` if `method` is not a buffer event.
+pub fn decode_buf_event(
+    method: &str,
+    params: rmpv::Value,
+) -> Option<Result<BufEvent, rmpv::ext::Error>> {
+    Some(match method {
+        LINES_EVENT => BufLines::deserialize(params).map(BufEvent::Lines),
+        CHANGEDTICK_EVENT => BufChangedTick::deserialize(params).map(BufEvent::ChangedTick),
+        DETACH_EVENT => BufDetach::deserialize(params).map(BufEvent::Detach),
+        _ => return None,
+    })
+}
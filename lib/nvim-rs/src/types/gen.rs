@@ -1,69 +1,92 @@
 use super::manual::*;
 use std::fmt::Display;
-#[derive(Debug, serde :: Deserialize)]
+use std::marker::PhantomData;
+#[derive(Debug)]
 pub struct ModeInfoSet {
     pub enabled: bool,
     pub cursor_styles: Vec<ModeInfo>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(ModeInfoSet {
+    enabled,
+    cursor_styles
+});
+#[derive(Debug)]
 pub struct ModeChange {
     pub mode: String,
     pub mode_idx: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(ModeChange { mode, mode_idx });
+#[derive(Debug)]
 pub struct SetTitle {
     pub title: String,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(SetTitle { title });
+#[derive(Debug)]
 pub struct SetIcon {
     pub icon: String,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(SetIcon { icon });
+#[derive(Debug)]
 pub struct Screenshot {
     pub path: String,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(Screenshot { path });
+#[derive(Debug)]
 pub struct UpdateFg {
     pub fg: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(UpdateFg { fg });
+#[derive(Debug)]
 pub struct UpdateBg {
     pub bg: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(UpdateBg { bg });
+#[derive(Debug)]
 pub struct UpdateSp {
     pub sp: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(UpdateSp { sp });
+#[derive(Debug)]
 pub struct Resize {
     pub width: i64,
     pub height: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(Resize { width, height });
+#[derive(Debug)]
 pub struct CursorGoto {
     pub row: i64,
     pub col: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(CursorGoto { row, col });
+#[derive(Debug)]
 pub struct HighlightSet {
     pub attrs: Dictionary,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(HighlightSet { attrs });
+#[derive(Debug)]
 pub struct Put {
     pub str: String,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(Put { str });
+#[derive(Debug)]
 pub struct SetScrollRegion {
     pub top: i64,
     pub bot: i64,
     pub left: i64,
     pub right: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(SetScrollRegion {
+    top,
+    bot,
+    left,
+    right
+});
+#[derive(Debug)]
 pub struct Scroll {
     pub count: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(Scroll { count });
+#[derive(Debug)]
 pub struct DefaultColorsSet {
     pub rgb_fg: i64,
     pub rgb_bg: i64,
@@ -71,42 +94,64 @@ pub struct DefaultColorsSet {
     pub cterm_fg: i64,
     pub cterm_bg: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(DefaultColorsSet {
+    rgb_fg,
+    rgb_bg,
+    rgb_sp,
+    cterm_fg,
+    cterm_bg
+});
+#[derive(Debug)]
 pub struct HlAttrDefine {
     pub id: i64,
     pub rgb_attrs: HlAttr,
     pub cterm_attrs: HlAttr,
     pub info: Vec<rmpv::Value>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(HlAttrDefine {
+    id,
+    rgb_attrs,
+    cterm_attrs,
+    info
+});
+#[derive(Debug)]
 pub struct HlGroupSet {
     pub name: String,
     pub id: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(HlGroupSet { name, id });
+#[derive(Debug)]
 pub struct GridResize {
     pub grid: i64,
     pub width: i64,
     pub height: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(GridResize {
+    grid,
+    width,
+    height
+});
+#[derive(Debug)]
 pub struct GridClear {
     pub grid: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(GridClear { grid });
+#[derive(Debug)]
 pub struct GridCursorGoto {
     pub grid: i64,
     pub row: i64,
     pub col: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
-pub struct GridLine {
+impl_seq_deserialize!(GridCursorGoto { grid, row, col });
+#[derive(Debug)]
+pub struct GridLine<'a> {
     pub grid: i64,
     pub row: i64,
     pub col_start: i64,
-    pub data: Vec<GridLineData>,
+    pub data: Vec<GridLineData<'a>>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(GridLine<'a> { grid, row, col_start, data });
+#[derive(Debug)]
 pub struct GridScroll {
     pub grid: i64,
     pub top: i64,
@@ -116,11 +161,21 @@ pub struct GridScroll {
     pub rows: i64,
     pub cols: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(GridScroll {
+    grid,
+    top,
+    bot,
+    left,
+    right,
+    rows,
+    cols
+});
+#[derive(Debug)]
 pub struct GridDestroy {
     pub grid: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(GridDestroy { grid });
+#[derive(Debug)]
 pub struct WinPos {
     pub grid: i64,
     pub win: Window,
@@ -129,7 +184,15 @@ pub struct WinPos {
     pub width: i64,
     pub height: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(WinPos {
+    grid,
+    win,
+    startrow,
+    startcol,
+    width,
+    height
+});
+#[derive(Debug)]
 pub struct WinFloatPos {
     pub grid: i64,
     pub win: Window,
@@ -140,37 +203,46 @@ pub struct WinFloatPos {
     pub focusable: bool,
     pub zindex: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(WinFloatPos {
+    grid,
+    win,
+    anchor,
+    anchor_grid,
+    anchor_row,
+    anchor_col,
+    focusable,
+    zindex
+});
+#[derive(Debug)]
 pub struct WinExternalPos {
     pub grid: i64,
     pub win: Window,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(WinExternalPos { grid, win });
+#[derive(Debug)]
 pub struct WinHide {
     pub grid: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(WinHide { grid });
+#[derive(Debug)]
 pub struct WinClose {
     pub grid: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(WinClose { grid });
+#[derive(Debug)]
 pub struct MsgSetPos {
     pub grid: i64,
     pub row: i64,
     pub scrolled: bool,
     pub sep_char: String,
 }
-#[derive(Debug, serde :: Deserialize)]
-pub struct WinViewport {
-    pub grid: i64,
-    pub win: Window,
-    pub topline: i64,
-    pub botline: i64,
-    pub curline: i64,
-    pub curcol: i64,
-    pub line_count: i64,
-}
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(MsgSetPos {
+    grid,
+    row,
+    scrolled,
+    sep_char
+});
+#[derive(Debug)]
 pub struct WinExtmark {
     pub grid: i64,
     pub win: Window,
@@ -179,7 +251,15 @@ pub struct WinExtmark {
     pub row: i64,
     pub col: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(WinExtmark {
+    grid,
+    win,
+    ns_id,
+    mark_id,
+    row,
+    col
+});
+#[derive(Debug)]
 pub struct PopupmenuShow {
     pub items: Vec<PopupmenuItem>,
     pub selected: i64,
@@ -187,18 +267,32 @@ pub struct PopupmenuShow {
     pub col: i64,
     pub grid: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(PopupmenuShow {
+    items,
+    selected,
+    row,
+    col,
+    grid
+});
+#[derive(Debug)]
 pub struct PopupmenuSelect {
     pub selected: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(PopupmenuSelect { selected });
+#[derive(Debug)]
 pub struct TablineUpdate {
     pub current: Tabpage,
     pub tabs: Vec<TablineTab>,
     pub current_buffer: Buffer,
     pub buffers: Vec<TablineBuffer>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(TablineUpdate {
+    current,
+    tabs,
+    current_buffer,
+    buffers
+});
+#[derive(Debug)]
 pub struct CmdlineShow {
     pub content: Vec<CmdlineContent>,
     pub pos: i64,
@@ -207,61 +301,85 @@ pub struct CmdlineShow {
     pub indent: i64,
     pub level: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(CmdlineShow {
+    content,
+    pos,
+    firstc,
+    prompt,
+    indent,
+    level
+});
+#[derive(Debug)]
 pub struct CmdlinePos {
     pub pos: i64,
     pub level: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(CmdlinePos { pos, level });
+#[derive(Debug)]
 pub struct CmdlineSpecialChar {
     pub c: String,
     pub shift: bool,
     pub level: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(CmdlineSpecialChar { c, shift, level });
+#[derive(Debug)]
 pub struct CmdlineHide {
     pub level: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(CmdlineHide { level });
+#[derive(Debug)]
 pub struct CmdlineBlockShow {
     pub lines: Vec<Vec<CmdlineContent>>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(CmdlineBlockShow { lines });
+#[derive(Debug)]
 pub struct CmdlineBlockAppend {
     pub lines: Vec<CmdlineContent>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(CmdlineBlockAppend { lines });
+#[derive(Debug)]
 pub struct WildmenuShow {
     pub items: Vec<rmpv::Value>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(WildmenuShow { items });
+#[derive(Debug)]
 pub struct WildmenuSelect {
     pub selected: i64,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(WildmenuSelect { selected });
+#[derive(Debug)]
 pub struct MsgShow {
     pub kind: String,
-    pub content: Vec<rmpv::Value>,
+    pub content: Vec<MsgContent>,
     pub replace_last: bool,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(MsgShow {
+    kind,
+    content,
+    replace_last
+});
+#[derive(Debug)]
 pub struct MsgShowcmd {
-    pub content: Vec<rmpv::Value>,
+    pub content: Vec<MsgContent>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(MsgShowcmd { content });
+#[derive(Debug)]
 pub struct MsgShowmode {
-    pub content: Vec<rmpv::Value>,
+    pub content: Vec<MsgContent>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(MsgShowmode { content });
+#[derive(Debug)]
 pub struct MsgRuler {
-    pub content: Vec<rmpv::Value>,
+    pub content: Vec<MsgContent>,
 }
-#[derive(Debug, serde :: Deserialize)]
+impl_seq_deserialize!(MsgRuler { content });
+#[derive(Debug)]
 pub struct MsgHistoryShow {
-    pub entries: Vec<rmpv::Value>,
+    pub entries: Vec<MsgHistoryEntry>,
 }
+impl_seq_deserialize!(MsgHistoryShow { entries });
 #[derive(Debug)]
-pub enum UiEvent {
+pub enum UiEvent<'a> {
     ModeInfoSet(Vec<ModeInfoSet>),
     UpdateMenu,
     BusyStart,
@@ -294,7 +412,7 @@ pub enum UiEvent {
     GridResize(Vec<GridResize>),
     GridClear(Vec<GridClear>),
     GridCursorGoto(Vec<GridCursorGoto>),
-    GridLine(Vec<GridLine>),
+    GridLine(Vec<GridLine<'a>>),
     GridScroll(Vec<GridScroll>),
     GridDestroy(Vec<GridDestroy>),
     WinPos(Vec<WinPos>),
@@ -326,8 +444,12 @@ pub enum UiEvent {
     MsgRuler(Vec<MsgRuler>),
     MsgHistoryShow(Vec<MsgHistoryShow>),
     MsgHistoryClear,
+    Unknown {
+        name: String,
+        params: Vec<rmpv::Value>,
+    },
 }
-impl Display for UiEvent {
+impl Display for UiEvent<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::ModeInfoSet(_) => write!(f, "mode_info_set"),
@@ -394,374 +516,220 @@ impl Display for UiEvent {
             Self::MsgRuler(_) => write!(f, "msg_ruler"),
             Self::MsgHistoryShow(_) => write!(f, "msg_history_show"),
             Self::MsgHistoryClear => write!(f, "msg_history_clear"),
+            Self::Unknown { name, .. } => write!(f, "{}", name),
         }
     }
 }
-impl<'de> serde::Deserialize<'de> for UiEvent {
+impl UiEvent<'_> {
+    #[doc = r" Converts the event into one that doesn't borrow from the"]
+    #[doc = r" decoded message."]
+    pub fn into_owned(self) -> UiEvent<'static> {
+        match self {
+            Self::ModeInfoSet(v) => UiEvent::ModeInfoSet(v),
+            Self::UpdateMenu => UiEvent::UpdateMenu,
+            Self::BusyStart => UiEvent::BusyStart,
+            Self::BusyStop => UiEvent::BusyStop,
+            Self::MouseOn => UiEvent::MouseOn,
+            Self::MouseOff => UiEvent::MouseOff,
+            Self::ModeChange(v) => UiEvent::ModeChange(v),
+            Self::Bell => UiEvent::Bell,
+            Self::VisualBell => UiEvent::VisualBell,
+            Self::Flush => UiEvent::Flush,
+            Self::Suspend => UiEvent::Suspend,
+            Self::SetTitle(v) => UiEvent::SetTitle(v),
+            Self::SetIcon(v) => UiEvent::SetIcon(v),
+            Self::Screenshot(v) => UiEvent::Screenshot(v),
+            Self::OptionSet(v) => UiEvent::OptionSet(v),
+            Self::UpdateFg(v) => UiEvent::UpdateFg(v),
+            Self::UpdateBg(v) => UiEvent::UpdateBg(v),
+            Self::UpdateSp(v) => UiEvent::UpdateSp(v),
+            Self::Resize(v) => UiEvent::Resize(v),
+            Self::Clear => UiEvent::Clear,
+            Self::EolClear => UiEvent::EolClear,
+            Self::CursorGoto(v) => UiEvent::CursorGoto(v),
+            Self::HighlightSet(v) => UiEvent::HighlightSet(v),
+            Self::Put(v) => UiEvent::Put(v),
+            Self::SetScrollRegion(v) => UiEvent::SetScrollRegion(v),
+            Self::Scroll(v) => UiEvent::Scroll(v),
+            Self::DefaultColorsSet(v) => UiEvent::DefaultColorsSet(v),
+            Self::HlAttrDefine(v) => UiEvent::HlAttrDefine(v),
+            Self::HlGroupSet(v) => UiEvent::HlGroupSet(v),
+            Self::GridResize(v) => UiEvent::GridResize(v),
+            Self::GridClear(v) => UiEvent::GridClear(v),
+            Self::GridCursorGoto(v) => UiEvent::GridCursorGoto(v),
+            Self::GridLine(v) => {
+                UiEvent::GridLine(v.into_iter().map(GridLine::into_owned).collect())
+            }
+            Self::GridScroll(v) => UiEvent::GridScroll(v),
+            Self::GridDestroy(v) => UiEvent::GridDestroy(v),
+            Self::WinPos(v) => UiEvent::WinPos(v),
+            Self::WinFloatPos(v) => UiEvent::WinFloatPos(v),
+            Self::WinExternalPos(v) => UiEvent::WinExternalPos(v),
+            Self::WinHide(v) => UiEvent::WinHide(v),
+            Self::WinClose(v) => UiEvent::WinClose(v),
+            Self::MsgSetPos(v) => UiEvent::MsgSetPos(v),
+            Self::WinViewport(v) => UiEvent::WinViewport(v),
+            Self::WinExtmark(v) => UiEvent::WinExtmark(v),
+            Self::PopupmenuShow(v) => UiEvent::PopupmenuShow(v),
+            Self::PopupmenuHide => UiEvent::PopupmenuHide,
+            Self::PopupmenuSelect(v) => UiEvent::PopupmenuSelect(v),
+            Self::TablineUpdate(v) => UiEvent::TablineUpdate(v),
+            Self::CmdlineShow(v) => UiEvent::CmdlineShow(v),
+            Self::CmdlinePos(v) => UiEvent::CmdlinePos(v),
+            Self::CmdlineSpecialChar(v) => UiEvent::CmdlineSpecialChar(v),
+            Self::CmdlineHide(v) => UiEvent::CmdlineHide(v),
+            Self::CmdlineBlockShow(v) => UiEvent::CmdlineBlockShow(v),
+            Self::CmdlineBlockAppend(v) => UiEvent::CmdlineBlockAppend(v),
+            Self::CmdlineBlockHide => UiEvent::CmdlineBlockHide,
+            Self::WildmenuShow(v) => UiEvent::WildmenuShow(v),
+            Self::WildmenuSelect(v) => UiEvent::WildmenuSelect(v),
+            Self::WildmenuHide => UiEvent::WildmenuHide,
+            Self::MsgShow(v) => UiEvent::MsgShow(v),
+            Self::MsgClear => UiEvent::MsgClear,
+            Self::MsgShowcmd(v) => UiEvent::MsgShowcmd(v),
+            Self::MsgShowmode(v) => UiEvent::MsgShowmode(v),
+            Self::MsgRuler(v) => UiEvent::MsgRuler(v),
+            Self::MsgHistoryShow(v) => UiEvent::MsgHistoryShow(v),
+            Self::MsgHistoryClear => UiEvent::MsgHistoryClear,
+            Self::Unknown { name, params } => UiEvent::Unknown { name, params },
+        }
+    }
+}
+impl<'de: 'a, 'a> serde::Deserialize<'de> for UiEvent<'a> {
     fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let redraw = rmpv::Value::deserialize(d)?;
-        let name = redraw[0].as_str();
-        let params = redraw.as_array().and_then(|v| {
-            if v[1].as_array().map(|v| v.is_empty()) == Some(true) {
-                None
-            } else {
-                Some(v[1..].to_vec())
+        struct Visitor<'a>(PhantomData<UiEvent<'a>>);
+        impl<'de: 'a, 'a> serde::de::Visitor<'de> for Visitor<'a> {
+            type Value = UiEvent<'a>;
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "ui event")
+            }
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let name = seq
+                    .next_element::<CowStr>()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+                Ok(match name.0.as_ref() {
+                    "mode_info_set" => UiEvent::ModeInfoSet(event_params(&mut seq)?),
+                    "update_menu" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::UpdateMenu
+                    }
+                    "busy_start" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::BusyStart
+                    }
+                    "busy_stop" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::BusyStop
+                    }
+                    "mouse_on" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::MouseOn
+                    }
+                    "mouse_off" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::MouseOff
+                    }
+                    "mode_change" => UiEvent::ModeChange(event_params(&mut seq)?),
+                    "bell" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::Bell
+                    }
+                    "visual_bell" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::VisualBell
+                    }
+                    "flush" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::Flush
+                    }
+                    "suspend" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::Suspend
+                    }
+                    "set_title" => UiEvent::SetTitle(event_params(&mut seq)?),
+                    "set_icon" => UiEvent::SetIcon(event_params(&mut seq)?),
+                    "screenshot" => UiEvent::Screenshot(event_params(&mut seq)?),
+                    "option_set" => UiEvent::OptionSet(event_params(&mut seq)?),
+                    "update_fg" => UiEvent::UpdateFg(event_params(&mut seq)?),
+                    "update_bg" => UiEvent::UpdateBg(event_params(&mut seq)?),
+                    "update_sp" => UiEvent::UpdateSp(event_params(&mut seq)?),
+                    "resize" => UiEvent::Resize(event_params(&mut seq)?),
+                    "clear" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::Clear
+                    }
+                    "eol_clear" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::EolClear
+                    }
+                    "cursor_goto" => UiEvent::CursorGoto(event_params(&mut seq)?),
+                    "highlight_set" => UiEvent::HighlightSet(event_params(&mut seq)?),
+                    "put" => UiEvent::Put(event_params(&mut seq)?),
+                    "set_scroll_region" => UiEvent::SetScrollRegion(event_params(&mut seq)?),
+                    "scroll" => UiEvent::Scroll(event_params(&mut seq)?),
+                    "default_colors_set" => UiEvent::DefaultColorsSet(event_params(&mut seq)?),
+                    "hl_attr_define" => UiEvent::HlAttrDefine(event_params(&mut seq)?),
+                    "hl_group_set" => UiEvent::HlGroupSet(event_params(&mut seq)?),
+                    "grid_resize" => UiEvent::GridResize(event_params(&mut seq)?),
+                    "grid_clear" => UiEvent::GridClear(event_params(&mut seq)?),
+                    "grid_cursor_goto" => UiEvent::GridCursorGoto(event_params(&mut seq)?),
+                    "grid_line" => UiEvent::GridLine(event_params(&mut seq)?),
+                    "grid_scroll" => UiEvent::GridScroll(event_params(&mut seq)?),
+                    "grid_destroy" => UiEvent::GridDestroy(event_params(&mut seq)?),
+                    "win_pos" => UiEvent::WinPos(event_params(&mut seq)?),
+                    "win_float_pos" => UiEvent::WinFloatPos(event_params(&mut seq)?),
+                    "win_external_pos" => UiEvent::WinExternalPos(event_params(&mut seq)?),
+                    "win_hide" => UiEvent::WinHide(event_params(&mut seq)?),
+                    "win_close" => UiEvent::WinClose(event_params(&mut seq)?),
+                    "msg_set_pos" => UiEvent::MsgSetPos(event_params(&mut seq)?),
+                    "win_viewport" => UiEvent::WinViewport(event_params(&mut seq)?),
+                    "win_extmark" => UiEvent::WinExtmark(event_params(&mut seq)?),
+                    "popupmenu_show" => UiEvent::PopupmenuShow(event_params(&mut seq)?),
+                    "popupmenu_hide" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::PopupmenuHide
+                    }
+                    "popupmenu_select" => UiEvent::PopupmenuSelect(event_params(&mut seq)?),
+                    "tabline_update" => UiEvent::TablineUpdate(event_params(&mut seq)?),
+                    "cmdline_show" => UiEvent::CmdlineShow(event_params(&mut seq)?),
+                    "cmdline_pos" => UiEvent::CmdlinePos(event_params(&mut seq)?),
+                    "cmdline_special_char" => UiEvent::CmdlineSpecialChar(event_params(&mut seq)?),
+                    "cmdline_hide" => UiEvent::CmdlineHide(event_params(&mut seq)?),
+                    "cmdline_block_show" => UiEvent::CmdlineBlockShow(event_params(&mut seq)?),
+                    "cmdline_block_append" => UiEvent::CmdlineBlockAppend(event_params(&mut seq)?),
+                    "cmdline_block_hide" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::CmdlineBlockHide
+                    }
+                    "wildmenu_show" => UiEvent::WildmenuShow(event_params(&mut seq)?),
+                    "wildmenu_select" => UiEvent::WildmenuSelect(event_params(&mut seq)?),
+                    "wildmenu_hide" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::WildmenuHide
+                    }
+                    "msg_show" => UiEvent::MsgShow(event_params(&mut seq)?),
+                    "msg_clear" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::MsgClear
+                    }
+                    "msg_showcmd" => UiEvent::MsgShowcmd(event_params(&mut seq)?),
+                    "msg_showmode" => UiEvent::MsgShowmode(event_params(&mut seq)?),
+                    "msg_ruler" => UiEvent::MsgRuler(event_params(&mut seq)?),
+                    "msg_history_show" => UiEvent::MsgHistoryShow(event_params(&mut seq)?),
+                    "msg_history_clear" => {
+                        skip_remaining(&mut seq)?;
+                        UiEvent::MsgHistoryClear
+                    }
+                    name => UiEvent::Unknown {
+                        name: name.to_string(),
+                        params: event_params(&mut seq)?,
+                    },
+                })
             }
-        });
-        Ok(match (name, params) {
-            (Some("mode_info_set"), Some(params)) => UiEvent::ModeInfoSet({
-                params
-                    .into_iter()
-                    .map(ModeInfoSet::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("update_menu"), None) => UiEvent::UpdateMenu,
-            (Some("busy_start"), None) => UiEvent::BusyStart,
-            (Some("busy_stop"), None) => UiEvent::BusyStop,
-            (Some("mouse_on"), None) => UiEvent::MouseOn,
-            (Some("mouse_off"), None) => UiEvent::MouseOff,
-            (Some("mode_change"), Some(params)) => UiEvent::ModeChange({
-                params
-                    .into_iter()
-                    .map(ModeChange::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("bell"), None) => UiEvent::Bell,
-            (Some("visual_bell"), None) => UiEvent::VisualBell,
-            (Some("flush"), None) => UiEvent::Flush,
-            (Some("suspend"), None) => UiEvent::Suspend,
-            (Some("set_title"), Some(params)) => UiEvent::SetTitle({
-                params
-                    .into_iter()
-                    .map(SetTitle::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("set_icon"), Some(params)) => UiEvent::SetIcon({
-                params
-                    .into_iter()
-                    .map(SetIcon::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("screenshot"), Some(params)) => UiEvent::Screenshot({
-                params
-                    .into_iter()
-                    .map(Screenshot::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("option_set"), Some(params)) => UiEvent::OptionSet({
-                params
-                    .into_iter()
-                    .map(OptionSet::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("update_fg"), Some(params)) => UiEvent::UpdateFg({
-                params
-                    .into_iter()
-                    .map(UpdateFg::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("update_bg"), Some(params)) => UiEvent::UpdateBg({
-                params
-                    .into_iter()
-                    .map(UpdateBg::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("update_sp"), Some(params)) => UiEvent::UpdateSp({
-                params
-                    .into_iter()
-                    .map(UpdateSp::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("resize"), Some(params)) => UiEvent::Resize({
-                params
-                    .into_iter()
-                    .map(Resize::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("clear"), None) => UiEvent::Clear,
-            (Some("eol_clear"), None) => UiEvent::EolClear,
-            (Some("cursor_goto"), Some(params)) => UiEvent::CursorGoto({
-                params
-                    .into_iter()
-                    .map(CursorGoto::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("highlight_set"), Some(params)) => UiEvent::HighlightSet({
-                params
-                    .into_iter()
-                    .map(HighlightSet::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("put"), Some(params)) => UiEvent::Put({
-                params
-                    .into_iter()
-                    .map(Put::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("set_scroll_region"), Some(params)) => UiEvent::SetScrollRegion({
-                params
-                    .into_iter()
-                    .map(SetScrollRegion::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("scroll"), Some(params)) => UiEvent::Scroll({
-                params
-                    .into_iter()
-                    .map(Scroll::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("default_colors_set"), Some(params)) => UiEvent::DefaultColorsSet({
-                params
-                    .into_iter()
-                    .map(DefaultColorsSet::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("hl_attr_define"), Some(params)) => UiEvent::HlAttrDefine({
-                params
-                    .into_iter()
-                    .map(HlAttrDefine::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("hl_group_set"), Some(params)) => UiEvent::HlGroupSet({
-                params
-                    .into_iter()
-                    .map(HlGroupSet::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("grid_resize"), Some(params)) => UiEvent::GridResize({
-                params
-                    .into_iter()
-                    .map(GridResize::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("grid_clear"), Some(params)) => UiEvent::GridClear({
-                params
-                    .into_iter()
-                    .map(GridClear::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("grid_cursor_goto"), Some(params)) => UiEvent::GridCursorGoto({
-                params
-                    .into_iter()
-                    .map(GridCursorGoto::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("grid_line"), Some(params)) => UiEvent::GridLine({
-                params
-                    .into_iter()
-                    .map(GridLine::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("grid_scroll"), Some(params)) => UiEvent::GridScroll({
-                params
-                    .into_iter()
-                    .map(GridScroll::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("grid_destroy"), Some(params)) => UiEvent::GridDestroy({
-                params
-                    .into_iter()
-                    .map(GridDestroy::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("win_pos"), Some(params)) => UiEvent::WinPos({
-                params
-                    .into_iter()
-                    .map(WinPos::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("win_float_pos"), Some(params)) => UiEvent::WinFloatPos({
-                params
-                    .into_iter()
-                    .map(WinFloatPos::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("win_external_pos"), Some(params)) => UiEvent::WinExternalPos({
-                params
-                    .into_iter()
-                    .map(WinExternalPos::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("win_hide"), Some(params)) => UiEvent::WinHide({
-                params
-                    .into_iter()
-                    .map(WinHide::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("win_close"), Some(params)) => UiEvent::WinClose({
-                params
-                    .into_iter()
-                    .map(WinClose::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("msg_set_pos"), Some(params)) => UiEvent::MsgSetPos({
-                params
-                    .into_iter()
-                    .map(MsgSetPos::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("win_viewport"), Some(params)) => UiEvent::WinViewport({
-                params
-                    .into_iter()
-                    .map(WinViewport::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("win_extmark"), Some(params)) => UiEvent::WinExtmark({
-                params
-                    .into_iter()
-                    .map(WinExtmark::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("popupmenu_show"), Some(params)) => UiEvent::PopupmenuShow({
-                params
-                    .into_iter()
-                    .map(PopupmenuShow::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("popupmenu_hide"), None) => UiEvent::PopupmenuHide,
-            (Some("popupmenu_select"), Some(params)) => UiEvent::PopupmenuSelect({
-                params
-                    .into_iter()
-                    .map(PopupmenuSelect::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("tabline_update"), Some(params)) => UiEvent::TablineUpdate({
-                params
-                    .into_iter()
-                    .map(TablineUpdate::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("cmdline_show"), Some(params)) => UiEvent::CmdlineShow({
-                params
-                    .into_iter()
-                    .map(CmdlineShow::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("cmdline_pos"), Some(params)) => UiEvent::CmdlinePos({
-                params
-                    .into_iter()
-                    .map(CmdlinePos::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("cmdline_special_char"), Some(params)) => UiEvent::CmdlineSpecialChar({
-                params
-                    .into_iter()
-                    .map(CmdlineSpecialChar::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("cmdline_hide"), Some(params)) => UiEvent::CmdlineHide({
-                params
-                    .into_iter()
-                    .map(CmdlineHide::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("cmdline_block_show"), Some(params)) => UiEvent::CmdlineBlockShow({
-                params
-                    .into_iter()
-                    .map(CmdlineBlockShow::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("cmdline_block_append"), Some(params)) => UiEvent::CmdlineBlockAppend({
-                params
-                    .into_iter()
-                    .map(CmdlineBlockAppend::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("cmdline_block_hide"), None) => UiEvent::CmdlineBlockHide,
-            (Some("wildmenu_show"), Some(params)) => UiEvent::WildmenuShow({
-                params
-                    .into_iter()
-                    .map(WildmenuShow::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("wildmenu_select"), Some(params)) => UiEvent::WildmenuSelect({
-                params
-                    .into_iter()
-                    .map(WildmenuSelect::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("wildmenu_hide"), None) => UiEvent::WildmenuHide,
-            (Some("msg_show"), Some(params)) => UiEvent::MsgShow({
-                params
-                    .into_iter()
-                    .map(MsgShow::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("msg_clear"), None) => UiEvent::MsgClear,
-            (Some("msg_showcmd"), Some(params)) => UiEvent::MsgShowcmd({
-                params
-                    .into_iter()
-                    .map(MsgShowcmd::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("msg_showmode"), Some(params)) => UiEvent::MsgShowmode({
-                params
-                    .into_iter()
-                    .map(MsgShowmode::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("msg_ruler"), Some(params)) => UiEvent::MsgRuler({
-                params
-                    .into_iter()
-                    .map(MsgRuler::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("msg_history_show"), Some(params)) => UiEvent::MsgHistoryShow({
-                params
-                    .into_iter()
-                    .map(MsgHistoryShow::deserialize)
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(serde::de::Error::custom)?
-            }),
-            (Some("msg_history_clear"), None) => UiEvent::MsgHistoryClear,
-            v => panic!("failed to decode message {:?}", v),
-        })
+        }
+        d.deserialize_seq(Visitor(PhantomData))
     }
 }
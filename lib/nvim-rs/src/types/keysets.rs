@@ -0,0 +1,1175 @@
+#[allow(unused_imports)]
+use super::{Buffer, Dictionary, LuaRef, Object, Tabpage, Window};
+use serde::Serialize;
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Context {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub types: ::std::option::Option<Vec<String>>,
+}
+impl Context {
+    pub fn types(mut self, value: Vec<String>) -> Self {
+        self.types = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct SetDecorationProvider {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub on_start: ::std::option::Option<LuaRef>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub on_buf: ::std::option::Option<LuaRef>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub on_win: ::std::option::Option<LuaRef>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub on_line: ::std::option::Option<LuaRef>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub on_end: ::std::option::Option<LuaRef>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub _on_hl_def: ::std::option::Option<LuaRef>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub _on_spell_nav: ::std::option::Option<LuaRef>,
+}
+impl SetDecorationProvider {
+    pub fn on_start(mut self, value: LuaRef) -> Self {
+        self.on_start = Some(value);
+        self
+    }
+    pub fn on_buf(mut self, value: LuaRef) -> Self {
+        self.on_buf = Some(value);
+        self
+    }
+    pub fn on_win(mut self, value: LuaRef) -> Self {
+        self.on_win = Some(value);
+        self
+    }
+    pub fn on_line(mut self, value: LuaRef) -> Self {
+        self.on_line = Some(value);
+        self
+    }
+    pub fn on_end(mut self, value: LuaRef) -> Self {
+        self.on_end = Some(value);
+        self
+    }
+    pub fn _on_hl_def(mut self, value: LuaRef) -> Self {
+        self._on_hl_def = Some(value);
+        self
+    }
+    pub fn _on_spell_nav(mut self, value: LuaRef) -> Self {
+        self._on_spell_nav = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct SetExtmark {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub id: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub end_line: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub end_row: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub end_col: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub hl_group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub virt_text: ::std::option::Option<Vec<rmpv::Value>>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub virt_text_pos: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub virt_text_win_col: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub virt_text_hide: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub hl_eol: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub hl_mode: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub ephemeral: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub priority: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub right_gravity: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub end_right_gravity: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub virt_lines: ::std::option::Option<Vec<rmpv::Value>>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub virt_lines_above: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub virt_lines_leftcol: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub strict: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub sign_text: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub sign_hl_group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub number_hl_group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub line_hl_group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub cursorline_hl_group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub conceal: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub ui_watched: ::std::option::Option<bool>,
+}
+impl SetExtmark {
+    pub fn id(mut self, value: i64) -> Self {
+        self.id = Some(value);
+        self
+    }
+    pub fn end_line(mut self, value: i64) -> Self {
+        self.end_line = Some(value);
+        self
+    }
+    pub fn end_row(mut self, value: i64) -> Self {
+        self.end_row = Some(value);
+        self
+    }
+    pub fn end_col(mut self, value: i64) -> Self {
+        self.end_col = Some(value);
+        self
+    }
+    pub fn hl_group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.hl_group = Some(Object::new(value));
+        self
+    }
+    pub fn virt_text(mut self, value: Vec<rmpv::Value>) -> Self {
+        self.virt_text = Some(value);
+        self
+    }
+    pub fn virt_text_pos(mut self, value: impl Into<String>) -> Self {
+        self.virt_text_pos = Some(value.into());
+        self
+    }
+    pub fn virt_text_win_col(mut self, value: i64) -> Self {
+        self.virt_text_win_col = Some(value);
+        self
+    }
+    pub fn virt_text_hide(mut self, value: bool) -> Self {
+        self.virt_text_hide = Some(value);
+        self
+    }
+    pub fn hl_eol(mut self, value: bool) -> Self {
+        self.hl_eol = Some(value);
+        self
+    }
+    pub fn hl_mode(mut self, value: impl Into<String>) -> Self {
+        self.hl_mode = Some(value.into());
+        self
+    }
+    pub fn ephemeral(mut self, value: bool) -> Self {
+        self.ephemeral = Some(value);
+        self
+    }
+    pub fn priority(mut self, value: i64) -> Self {
+        self.priority = Some(value);
+        self
+    }
+    pub fn right_gravity(mut self, value: bool) -> Self {
+        self.right_gravity = Some(value);
+        self
+    }
+    pub fn end_right_gravity(mut self, value: bool) -> Self {
+        self.end_right_gravity = Some(value);
+        self
+    }
+    pub fn virt_lines(mut self, value: Vec<rmpv::Value>) -> Self {
+        self.virt_lines = Some(value);
+        self
+    }
+    pub fn virt_lines_above(mut self, value: bool) -> Self {
+        self.virt_lines_above = Some(value);
+        self
+    }
+    pub fn virt_lines_leftcol(mut self, value: bool) -> Self {
+        self.virt_lines_leftcol = Some(value);
+        self
+    }
+    pub fn strict(mut self, value: bool) -> Self {
+        self.strict = Some(value);
+        self
+    }
+    pub fn sign_text(mut self, value: impl Into<String>) -> Self {
+        self.sign_text = Some(value.into());
+        self
+    }
+    pub fn sign_hl_group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.sign_hl_group = Some(Object::new(value));
+        self
+    }
+    pub fn number_hl_group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.number_hl_group = Some(Object::new(value));
+        self
+    }
+    pub fn line_hl_group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.line_hl_group = Some(Object::new(value));
+        self
+    }
+    pub fn cursorline_hl_group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.cursorline_hl_group = Some(Object::new(value));
+        self
+    }
+    pub fn conceal(mut self, value: impl Into<String>) -> Self {
+        self.conceal = Some(value.into());
+        self
+    }
+    pub fn ui_watched(mut self, value: bool) -> Self {
+        self.ui_watched = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Keymap {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub noremap: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub nowait: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub silent: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub script: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub expr: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub unique: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub callback: ::std::option::Option<LuaRef>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub desc: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub replace_keycodes: ::std::option::Option<bool>,
+}
+impl Keymap {
+    pub fn noremap(mut self, value: bool) -> Self {
+        self.noremap = Some(value);
+        self
+    }
+    pub fn nowait(mut self, value: bool) -> Self {
+        self.nowait = Some(value);
+        self
+    }
+    pub fn silent(mut self, value: bool) -> Self {
+        self.silent = Some(value);
+        self
+    }
+    pub fn script(mut self, value: bool) -> Self {
+        self.script = Some(value);
+        self
+    }
+    pub fn expr(mut self, value: bool) -> Self {
+        self.expr = Some(value);
+        self
+    }
+    pub fn unique(mut self, value: bool) -> Self {
+        self.unique = Some(value);
+        self
+    }
+    pub fn callback(mut self, value: LuaRef) -> Self {
+        self.callback = Some(value);
+        self
+    }
+    pub fn desc(mut self, value: impl Into<String>) -> Self {
+        self.desc = Some(value.into());
+        self
+    }
+    pub fn replace_keycodes(mut self, value: bool) -> Self {
+        self.replace_keycodes = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct GetCommands {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub builtin: ::std::option::Option<bool>,
+}
+impl GetCommands {
+    pub fn builtin(mut self, value: bool) -> Self {
+        self.builtin = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct UserCommand {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub addr: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bang: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bar: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub complete: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub count: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub desc: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub force: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub keepscript: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub nargs: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub preview: ::std::option::Option<LuaRef>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub range: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub register: ::std::option::Option<bool>,
+}
+impl UserCommand {
+    pub fn addr(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.addr = Some(Object::new(value));
+        self
+    }
+    pub fn bang(mut self, value: bool) -> Self {
+        self.bang = Some(value);
+        self
+    }
+    pub fn bar(mut self, value: bool) -> Self {
+        self.bar = Some(value);
+        self
+    }
+    pub fn complete(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.complete = Some(Object::new(value));
+        self
+    }
+    pub fn count(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.count = Some(Object::new(value));
+        self
+    }
+    pub fn desc(mut self, value: impl Into<String>) -> Self {
+        self.desc = Some(value.into());
+        self
+    }
+    pub fn force(mut self, value: bool) -> Self {
+        self.force = Some(value);
+        self
+    }
+    pub fn keepscript(mut self, value: bool) -> Self {
+        self.keepscript = Some(value);
+        self
+    }
+    pub fn nargs(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.nargs = Some(Object::new(value));
+        self
+    }
+    pub fn preview(mut self, value: LuaRef) -> Self {
+        self.preview = Some(value);
+        self
+    }
+    pub fn range(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.range = Some(Object::new(value));
+        self
+    }
+    pub fn register(mut self, value: bool) -> Self {
+        self.register = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct FloatConfig {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub row: ::std::option::Option<f64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub col: ::std::option::Option<f64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub width: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub height: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub anchor: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub relative: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub win: ::std::option::Option<Window>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bufpos: ::std::option::Option<Vec<rmpv::Value>>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub external: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub focusable: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zindex: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub border: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub style: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub noautocmd: ::std::option::Option<bool>,
+}
+impl FloatConfig {
+    pub fn row(mut self, value: f64) -> Self {
+        self.row = Some(value);
+        self
+    }
+    pub fn col(mut self, value: f64) -> Self {
+        self.col = Some(value);
+        self
+    }
+    pub fn width(mut self, value: i64) -> Self {
+        self.width = Some(value);
+        self
+    }
+    pub fn height(mut self, value: i64) -> Self {
+        self.height = Some(value);
+        self
+    }
+    pub fn anchor(mut self, value: impl Into<String>) -> Self {
+        self.anchor = Some(value.into());
+        self
+    }
+    pub fn relative(mut self, value: impl Into<String>) -> Self {
+        self.relative = Some(value.into());
+        self
+    }
+    pub fn win(mut self, value: Window) -> Self {
+        self.win = Some(value);
+        self
+    }
+    pub fn bufpos(mut self, value: Vec<rmpv::Value>) -> Self {
+        self.bufpos = Some(value);
+        self
+    }
+    pub fn external(mut self, value: bool) -> Self {
+        self.external = Some(value);
+        self
+    }
+    pub fn focusable(mut self, value: bool) -> Self {
+        self.focusable = Some(value);
+        self
+    }
+    pub fn zindex(mut self, value: i64) -> Self {
+        self.zindex = Some(value);
+        self
+    }
+    pub fn border(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.border = Some(Object::new(value));
+        self
+    }
+    pub fn style(mut self, value: impl Into<String>) -> Self {
+        self.style = Some(value.into());
+        self
+    }
+    pub fn noautocmd(mut self, value: bool) -> Self {
+        self.noautocmd = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Runtime {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub is_lua: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub do_source: ::std::option::Option<bool>,
+}
+impl Runtime {
+    pub fn is_lua(mut self, value: bool) -> Self {
+        self.is_lua = Some(value);
+        self
+    }
+    pub fn do_source(mut self, value: bool) -> Self {
+        self.do_source = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct EvalStatusline {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub winid: ::std::option::Option<Window>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub maxwidth: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub fillchar: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub highlights: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub use_winbar: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub use_tabline: ::std::option::Option<bool>,
+}
+impl EvalStatusline {
+    pub fn winid(mut self, value: Window) -> Self {
+        self.winid = Some(value);
+        self
+    }
+    pub fn maxwidth(mut self, value: i64) -> Self {
+        self.maxwidth = Some(value);
+        self
+    }
+    pub fn fillchar(mut self, value: impl Into<String>) -> Self {
+        self.fillchar = Some(value.into());
+        self
+    }
+    pub fn highlights(mut self, value: bool) -> Self {
+        self.highlights = Some(value);
+        self
+    }
+    pub fn use_winbar(mut self, value: bool) -> Self {
+        self.use_winbar = Some(value);
+        self
+    }
+    pub fn use_tabline(mut self, value: bool) -> Self {
+        self.use_tabline = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Option {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub scope: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub win: ::std::option::Option<Window>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub buf: ::std::option::Option<Buffer>,
+}
+impl Option {
+    pub fn scope(mut self, value: impl Into<String>) -> Self {
+        self.scope = Some(value.into());
+        self
+    }
+    pub fn win(mut self, value: Window) -> Self {
+        self.win = Some(value);
+        self
+    }
+    pub fn buf(mut self, value: Buffer) -> Self {
+        self.buf = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Highlight {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bold: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub standout: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub strikethrough: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub underline: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub undercurl: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub underdouble: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub underdotted: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub underdashed: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub italic: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub reverse: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub nocombine: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub default: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub cterm: ::std::option::Option<HighlightCterm>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub foreground: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub fg: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub background: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bg: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub ctermfg: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub ctermbg: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub special: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub sp: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub link: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub global_link: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub fallback: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub blend: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub fg_indexed: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bg_indexed: ::std::option::Option<bool>,
+}
+impl Highlight {
+    pub fn bold(mut self, value: bool) -> Self {
+        self.bold = Some(value);
+        self
+    }
+    pub fn standout(mut self, value: bool) -> Self {
+        self.standout = Some(value);
+        self
+    }
+    pub fn strikethrough(mut self, value: bool) -> Self {
+        self.strikethrough = Some(value);
+        self
+    }
+    pub fn underline(mut self, value: bool) -> Self {
+        self.underline = Some(value);
+        self
+    }
+    pub fn undercurl(mut self, value: bool) -> Self {
+        self.undercurl = Some(value);
+        self
+    }
+    pub fn underdouble(mut self, value: bool) -> Self {
+        self.underdouble = Some(value);
+        self
+    }
+    pub fn underdotted(mut self, value: bool) -> Self {
+        self.underdotted = Some(value);
+        self
+    }
+    pub fn underdashed(mut self, value: bool) -> Self {
+        self.underdashed = Some(value);
+        self
+    }
+    pub fn italic(mut self, value: bool) -> Self {
+        self.italic = Some(value);
+        self
+    }
+    pub fn reverse(mut self, value: bool) -> Self {
+        self.reverse = Some(value);
+        self
+    }
+    pub fn nocombine(mut self, value: bool) -> Self {
+        self.nocombine = Some(value);
+        self
+    }
+    pub fn default(mut self, value: bool) -> Self {
+        self.default = Some(value);
+        self
+    }
+    pub fn cterm(mut self, value: HighlightCterm) -> Self {
+        self.cterm = Some(value);
+        self
+    }
+    pub fn foreground(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.foreground = Some(Object::new(value));
+        self
+    }
+    pub fn fg(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.fg = Some(Object::new(value));
+        self
+    }
+    pub fn background(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.background = Some(Object::new(value));
+        self
+    }
+    pub fn bg(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.bg = Some(Object::new(value));
+        self
+    }
+    pub fn ctermfg(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.ctermfg = Some(Object::new(value));
+        self
+    }
+    pub fn ctermbg(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.ctermbg = Some(Object::new(value));
+        self
+    }
+    pub fn special(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.special = Some(Object::new(value));
+        self
+    }
+    pub fn sp(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.sp = Some(Object::new(value));
+        self
+    }
+    pub fn link(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.link = Some(Object::new(value));
+        self
+    }
+    pub fn global_link(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.global_link = Some(Object::new(value));
+        self
+    }
+    pub fn fallback(mut self, value: bool) -> Self {
+        self.fallback = Some(value);
+        self
+    }
+    pub fn blend(mut self, value: i64) -> Self {
+        self.blend = Some(value);
+        self
+    }
+    pub fn fg_indexed(mut self, value: bool) -> Self {
+        self.fg_indexed = Some(value);
+        self
+    }
+    pub fn bg_indexed(mut self, value: bool) -> Self {
+        self.bg_indexed = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct HighlightCterm {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bold: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub standout: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub strikethrough: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub underline: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub undercurl: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub underdouble: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub underdotted: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub underdashed: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub italic: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub reverse: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub nocombine: ::std::option::Option<bool>,
+}
+impl HighlightCterm {
+    pub fn bold(mut self, value: bool) -> Self {
+        self.bold = Some(value);
+        self
+    }
+    pub fn standout(mut self, value: bool) -> Self {
+        self.standout = Some(value);
+        self
+    }
+    pub fn strikethrough(mut self, value: bool) -> Self {
+        self.strikethrough = Some(value);
+        self
+    }
+    pub fn underline(mut self, value: bool) -> Self {
+        self.underline = Some(value);
+        self
+    }
+    pub fn undercurl(mut self, value: bool) -> Self {
+        self.undercurl = Some(value);
+        self
+    }
+    pub fn underdouble(mut self, value: bool) -> Self {
+        self.underdouble = Some(value);
+        self
+    }
+    pub fn underdotted(mut self, value: bool) -> Self {
+        self.underdotted = Some(value);
+        self
+    }
+    pub fn underdashed(mut self, value: bool) -> Self {
+        self.underdashed = Some(value);
+        self
+    }
+    pub fn italic(mut self, value: bool) -> Self {
+        self.italic = Some(value);
+        self
+    }
+    pub fn reverse(mut self, value: bool) -> Self {
+        self.reverse = Some(value);
+        self
+    }
+    pub fn nocombine(mut self, value: bool) -> Self {
+        self.nocombine = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ClearAutocmds {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub buffer: ::std::option::Option<Buffer>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub event: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub pattern: ::std::option::Option<Object>,
+}
+impl ClearAutocmds {
+    pub fn buffer(mut self, value: Buffer) -> Self {
+        self.buffer = Some(value);
+        self
+    }
+    pub fn event(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.event = Some(Object::new(value));
+        self
+    }
+    pub fn group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.group = Some(Object::new(value));
+        self
+    }
+    pub fn pattern(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.pattern = Some(Object::new(value));
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct CreateAutocmd {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub buffer: ::std::option::Option<Buffer>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub callback: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub command: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub desc: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub nested: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub once: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub pattern: ::std::option::Option<Object>,
+}
+impl CreateAutocmd {
+    pub fn buffer(mut self, value: Buffer) -> Self {
+        self.buffer = Some(value);
+        self
+    }
+    pub fn callback(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.callback = Some(Object::new(value));
+        self
+    }
+    pub fn command(mut self, value: impl Into<String>) -> Self {
+        self.command = Some(value.into());
+        self
+    }
+    pub fn desc(mut self, value: impl Into<String>) -> Self {
+        self.desc = Some(value.into());
+        self
+    }
+    pub fn group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.group = Some(Object::new(value));
+        self
+    }
+    pub fn nested(mut self, value: bool) -> Self {
+        self.nested = Some(value);
+        self
+    }
+    pub fn once(mut self, value: bool) -> Self {
+        self.once = Some(value);
+        self
+    }
+    pub fn pattern(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.pattern = Some(Object::new(value));
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ExecAutocmds {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub buffer: ::std::option::Option<Buffer>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub modeline: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub pattern: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub data: ::std::option::Option<Object>,
+}
+impl ExecAutocmds {
+    pub fn buffer(mut self, value: Buffer) -> Self {
+        self.buffer = Some(value);
+        self
+    }
+    pub fn group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.group = Some(Object::new(value));
+        self
+    }
+    pub fn modeline(mut self, value: bool) -> Self {
+        self.modeline = Some(value);
+        self
+    }
+    pub fn pattern(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.pattern = Some(Object::new(value));
+        self
+    }
+    pub fn data(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.data = Some(Object::new(value));
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct GetAutocmds {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub event: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub group: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub pattern: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub buffer: ::std::option::Option<Object>,
+}
+impl GetAutocmds {
+    pub fn event(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.event = Some(Object::new(value));
+        self
+    }
+    pub fn group(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.group = Some(Object::new(value));
+        self
+    }
+    pub fn pattern(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.pattern = Some(Object::new(value));
+        self
+    }
+    pub fn buffer(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.buffer = Some(Object::new(value));
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct CreateAugroup {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub clear: ::std::option::Option<bool>,
+}
+impl CreateAugroup {
+    pub fn clear(mut self, value: bool) -> Self {
+        self.clear = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Cmd {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub cmd: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub range: ::std::option::Option<Vec<rmpv::Value>>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub count: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub reg: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bang: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub args: ::std::option::Option<Vec<rmpv::Value>>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub magic: ::std::option::Option<CmdMagic>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub mods: ::std::option::Option<CmdMods>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub nargs: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub addr: ::std::option::Option<Object>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub nextcmd: ::std::option::Option<Object>,
+}
+impl Cmd {
+    pub fn cmd(mut self, value: impl Into<String>) -> Self {
+        self.cmd = Some(value.into());
+        self
+    }
+    pub fn range(mut self, value: Vec<rmpv::Value>) -> Self {
+        self.range = Some(value);
+        self
+    }
+    pub fn count(mut self, value: i64) -> Self {
+        self.count = Some(value);
+        self
+    }
+    pub fn reg(mut self, value: impl Into<String>) -> Self {
+        self.reg = Some(value.into());
+        self
+    }
+    pub fn bang(mut self, value: bool) -> Self {
+        self.bang = Some(value);
+        self
+    }
+    pub fn args(mut self, value: Vec<rmpv::Value>) -> Self {
+        self.args = Some(value);
+        self
+    }
+    pub fn magic(mut self, value: CmdMagic) -> Self {
+        self.magic = Some(value);
+        self
+    }
+    pub fn mods(mut self, value: CmdMods) -> Self {
+        self.mods = Some(value);
+        self
+    }
+    pub fn nargs(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.nargs = Some(Object::new(value));
+        self
+    }
+    pub fn addr(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.addr = Some(Object::new(value));
+        self
+    }
+    pub fn nextcmd(mut self, value: impl Into<rmpv::Value>) -> Self {
+        self.nextcmd = Some(Object::new(value));
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct CmdMagic {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub file: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bar: ::std::option::Option<bool>,
+}
+impl CmdMagic {
+    pub fn file(mut self, value: bool) -> Self {
+        self.file = Some(value);
+        self
+    }
+    pub fn bar(mut self, value: bool) -> Self {
+        self.bar = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct CmdMods {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub silent: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub emsg_silent: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub unsilent: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub filter: ::std::option::Option<CmdModsFilter>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub sandbox: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub noautocmd: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub browse: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub confirm: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub hide: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub horizontal: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub keepalt: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub keepjumps: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub keepmarks: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub keeppatterns: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub lockmarks: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub noswapfile: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub tab: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub verbose: ::std::option::Option<i64>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vertical: ::std::option::Option<bool>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub split: ::std::option::Option<String>,
+}
+impl CmdMods {
+    pub fn silent(mut self, value: bool) -> Self {
+        self.silent = Some(value);
+        self
+    }
+    pub fn emsg_silent(mut self, value: bool) -> Self {
+        self.emsg_silent = Some(value);
+        self
+    }
+    pub fn unsilent(mut self, value: bool) -> Self {
+        self.unsilent = Some(value);
+        self
+    }
+    pub fn filter(mut self, value: CmdModsFilter) -> Self {
+        self.filter = Some(value);
+        self
+    }
+    pub fn sandbox(mut self, value: bool) -> Self {
+        self.sandbox = Some(value);
+        self
+    }
+    pub fn noautocmd(mut self, value: bool) -> Self {
+        self.noautocmd = Some(value);
+        self
+    }
+    pub fn browse(mut self, value: bool) -> Self {
+        self.browse = Some(value);
+        self
+    }
+    pub fn confirm(mut self, value: bool) -> Self {
+        self.confirm = Some(value);
+        self
+    }
+    pub fn hide(mut self, value: bool) -> Self {
+        self.hide = Some(value);
+        self
+    }
+    pub fn horizontal(mut self, value: bool) -> Self {
+        self.horizontal = Some(value);
+        self
+    }
+    pub fn keepalt(mut self, value: bool) -> Self {
+        self.keepalt = Some(value);
+        self
+    }
+    pub fn keepjumps(mut self, value: bool) -> Self {
+        self.keepjumps = Some(value);
+        self
+    }
+    pub fn keepmarks(mut self, value: bool) -> Self {
+        self.keepmarks = Some(value);
+        self
+    }
+    pub fn keeppatterns(mut self, value: bool) -> Self {
+        self.keeppatterns = Some(value);
+        self
+    }
+    pub fn lockmarks(mut self, value: bool) -> Self {
+        self.lockmarks = Some(value);
+        self
+    }
+    pub fn noswapfile(mut self, value: bool) -> Self {
+        self.noswapfile = Some(value);
+        self
+    }
+    pub fn tab(mut self, value: i64) -> Self {
+        self.tab = Some(value);
+        self
+    }
+    pub fn verbose(mut self, value: i64) -> Self {
+        self.verbose = Some(value);
+        self
+    }
+    pub fn vertical(mut self, value: bool) -> Self {
+        self.vertical = Some(value);
+        self
+    }
+    pub fn split(mut self, value: impl Into<String>) -> Self {
+        self.split = Some(value.into());
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct CmdModsFilter {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub pattern: ::std::option::Option<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub force: ::std::option::Option<bool>,
+}
+impl CmdModsFilter {
+    pub fn pattern(mut self, value: impl Into<String>) -> Self {
+        self.pattern = Some(value.into());
+        self
+    }
+    pub fn force(mut self, value: bool) -> Self {
+        self.force = Some(value);
+        self
+    }
+}
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct CmdOpts {
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub output: ::std::option::Option<bool>,
+}
+impl CmdOpts {
+    pub fn output(mut self, value: bool) -> Self {
+        self.output = Some(value);
+        self
+    }
+}
@@ -1,3 +1,8 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use super::uievents::GridLine;
+
 #[derive(Debug, Clone, Copy, Default)]
 pub enum ShowTabline {
     #[default]
@@ -16,35 +21,55 @@ pub enum OptionSet {
 
 impl<'de> serde::Deserialize<'de> for OptionSet {
     fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let data = rmpv::Value::deserialize(d)?;
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = OptionSet;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "option_set params")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let name = seq
+                    .next_element::<CowStr>()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+
+                let missing_value = || serde::de::Error::invalid_length(1, &self);
 
-        let bad_value = || serde::de::Error::missing_field("value");
-
-        let name = data[0]
-            .as_str()
-            .ok_or_else(|| serde::de::Error::custom("bad name"))?;
-
-        match name {
-            "linespace" => Ok(Self::Linespace(data[1].as_i64().ok_or_else(bad_value)?)),
-            "guifont" => Ok(Self::Guifont(
-                data[1].as_str().ok_or_else(bad_value)?.to_string(),
-            )),
-            "showtabline" => Ok(Self::ShowTabline(
-                data[1]
-                    .as_i64()
-                    .ok_or_else(bad_value)
-                    .and_then(|v| match v {
-                        0 => Ok(ShowTabline::Never),
-                        1 => Ok(ShowTabline::MoreThanOne),
-                        2 => Ok(ShowTabline::Always),
-                        _ => Err(serde::de::Error::custom(format!(
-                            "unexpected showtabline value: {:?}",
-                            v,
-                        ))),
-                    })?,
-            )),
-            _ => Ok(Self::Unknown(name.to_string())),
+                let option = match name.0.as_ref() {
+                    "linespace" => {
+                        OptionSet::Linespace(seq.next_element::<i64>()?.ok_or_else(missing_value)?)
+                    }
+                    "guifont" => {
+                        OptionSet::Guifont(seq.next_element::<String>()?.ok_or_else(missing_value)?)
+                    }
+                    "showtabline" => {
+                        let v = seq.next_element::<i64>()?.ok_or_else(missing_value)?;
+                        OptionSet::ShowTabline(match v {
+                            0 => ShowTabline::Never,
+                            1 => ShowTabline::MoreThanOne,
+                            2 => ShowTabline::Always,
+                            _ => {
+                                return Err(serde::de::Error::custom(format!(
+                                    "unexpected showtabline value: {:?}",
+                                    v,
+                                )))
+                            }
+                        })
+                    }
+                    name => OptionSet::Unknown(name.to_string()),
+                };
+
+                skip_remaining(&mut seq)?;
+                Ok(option)
+            }
         }
+
+        d.deserialize_seq(Visitor)
     }
 }
 
@@ -65,6 +90,32 @@ pub struct UiOptions {
     pub stdin_fd: Option<i32>,
 }
 
+impl UiOptions {
+    /// Turns off the ui extensions that are not in `supported` (e.g. the
+    /// `ui_options` of `ApiMetadata`). Returns the extensions turned off.
+    pub fn retain_supported<S: AsRef<str>>(&mut self, supported: &[S]) -> Vec<&'static str> {
+        let is_supported = |name: &str| supported.iter().any(|s| s.as_ref() == name);
+
+        [
+            ("ext_cmdline", &mut self.ext_cmdline),
+            ("ext_hlstate", &mut self.ext_hlstate),
+            ("ext_linegrid", &mut self.ext_linegrid),
+            ("ext_messages", &mut self.ext_messages),
+            ("ext_multigrid", &mut self.ext_multigrid),
+            ("ext_popupmenu", &mut self.ext_popupmenu),
+            ("ext_tabline", &mut self.ext_tabline),
+            ("ext_termcolors", &mut self.ext_termcolors),
+        ]
+        .into_iter()
+        .filter(|(name, enabled)| **enabled && !is_supported(name))
+        .map(|(name, enabled)| {
+            *enabled = false;
+            name
+        })
+        .collect()
+    }
+}
+
 #[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct HlAttr {
     pub foreground: Option<i64>,
@@ -82,28 +133,220 @@ pub struct HlAttr {
     pub blend: Option<i64>,
 }
 
-#[derive(Debug, Default)]
-pub struct GridLineData {
-    pub text: String,
+#[derive(Debug, Default, Clone)]
+pub struct GridLineData<'a> {
+    /// The cell's text. Borrowed from the decoded message when possible.
+    pub text: Cow<'a, str>,
     pub hl_id: Option<i64>,
     pub repeat: Option<i64>,
 }
 
-impl<'de> serde::Deserialize<'de> for GridLineData {
+impl GridLineData<'_> {
+    pub fn into_owned(self) -> GridLineData<'static> {
+        GridLineData {
+            text: Cow::Owned(self.text.into_owned()),
+            hl_id: self.hl_id,
+            repeat: self.repeat,
+        }
+    }
+}
+
+impl<'de: 'a, 'a> serde::Deserialize<'de> for GridLineData<'a> {
     fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let data = rmpv::Value::deserialize(d)?;
+        struct Visitor<'a>(PhantomData<GridLineData<'a>>);
 
-        let text = data[0]
-            .as_str()
-            .ok_or_else(|| serde::de::Error::custom(format!("bad text field: {:?}", data[0])))?;
-        let hl_id = data[1].as_i64();
-        let repeat = data[2].as_i64();
+        impl<'de: 'a, 'a> serde::de::Visitor<'de> for Visitor<'a> {
+            type Value = GridLineData<'a>;
 
-        Ok(Self {
-            text: text.to_string(),
-            hl_id,
-            repeat,
-        })
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "grid_line cell")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let text = seq
+                    .next_element::<CowStr>()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+
+                let data = GridLineData {
+                    text: text.0,
+                    hl_id: seq.next_element()?,
+                    repeat: seq.next_element()?,
+                };
+
+                skip_remaining(&mut seq)?;
+                Ok(data)
+            }
+        }
+
+        d.deserialize_seq(Visitor(PhantomData))
+    }
+}
+
+impl GridLine<'_> {
+    pub fn into_owned(self) -> GridLine<'static> {
+        GridLine {
+            grid: self.grid,
+            row: self.row,
+            col_start: self.col_start,
+            data: self
+                .data
+                .into_iter()
+                .map(GridLineData::into_owned)
+                .collect(),
+        }
+    }
+}
+
+/// String that is borrowed from the deserializer's input when possible.
+pub(crate) struct CowStr<'a>(pub Cow<'a, str>);
+
+impl<'de: 'a, 'a> serde::Deserialize<'de> for CowStr<'a> {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = CowStr<'de>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a string")
+            }
+
+            fn visit_borrowed_str<E: serde::de::Error>(
+                self,
+                v: &'de str,
+            ) -> Result<Self::Value, E> {
+                Ok(CowStr(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(CowStr(Cow::Owned(v.to_string())))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(CowStr(Cow::Owned(v)))
+            }
+        }
+
+        d.deserialize_str(Visitor)
+    }
+}
+
+/// Decodes the rest of an ui event's elements as its params.
+pub(crate) fn event_params<'de, A, T>(seq: &mut A) -> Result<Vec<T>, A::Error>
+where
+    A: serde::de::SeqAccess<'de>,
+    T: serde::Deserialize<'de>,
+{
+    let mut params = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+    while let Some(param) = seq.next_element()? {
+        params.push(param);
+    }
+
+    Ok(params)
+}
+
+/// Skips the rest of the sequence's elements (e.g. the params of an ui event
+/// that doesn't have any, or values added in newer versions of neovim).
+pub(crate) fn skip_remaining<'de, A>(seq: &mut A) -> Result<(), A::Error>
+where
+    A: serde::de::SeqAccess<'de>,
+{
+    while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+    Ok(())
+}
+
+/// Implements `Deserialize` for a struct that is encoded as a sequence (e.g.
+/// the params of an ui event). The fields are decoded in the given order, and
+/// any extra trailing values (e.g. params added in newer versions of neovim)
+/// are ignored. Fields listed after `;` are `Option`s that are `None` when
+/// missing (e.g. params not sent by older versions of neovim).
+macro_rules! impl_seq_deserialize {
+    ($name:ident $(<$lt:lifetime>)? { $($field:ident),* $(,)? $(; $($opt:ident),* $(,)?)? }) => {
+        impl<'de $(: $lt, $lt)?> serde::Deserialize<'de> for $name $(<$lt>)? {
+            fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                struct Visitor $(<$lt>)? (std::marker::PhantomData<$name $(<$lt>)?>);
+
+                impl<'de $(: $lt, $lt)?> serde::de::Visitor<'de> for Visitor $(<$lt>)? {
+                    type Value = $name $(<$lt>)?;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        write!(f, "{} as a sequence", stringify!($name))
+                    }
+
+                    #[allow(unused_assignments, unused_mut, unused_variables)]
+                    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                        self,
+                        mut seq: A,
+                    ) -> Result<Self::Value, A::Error> {
+                        let mut index = 0;
+                        let value = $name {
+                            $($field: {
+                                let field = seq
+                                    .next_element()?
+                                    .ok_or_else(|| serde::de::Error::invalid_length(index, &self))?;
+                                index += 1;
+                                field
+                            },)*
+                            $($($opt: seq.next_element()?.flatten(),)*)?
+                        };
+
+                        skip_remaining(&mut seq)?;
+                        Ok(value)
+                    }
+                }
+
+                d.deserialize_seq(Visitor(std::marker::PhantomData))
+            }
+        }
+    };
+}
+
+pub(crate) use impl_seq_deserialize;
+
+/// Result of `nvim_get_api_info`.
+#[derive(Debug)]
+pub struct ApiInfo {
+    pub channel: i64,
+    pub metadata: ApiMetadata,
+}
+
+impl_seq_deserialize!(ApiInfo { channel, metadata });
+
+/// The parts of the api metadata that are needed at runtime. The rest is
+/// used by `apigen`.
+#[derive(Debug, serde::Deserialize)]
+pub struct ApiMetadata {
+    pub version: ApiVersion,
+    /// Options (e.g. ui extensions) supported by `nvim_ui_attach`.
+    #[serde(default)]
+    pub ui_options: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ApiVersion {
+    pub major: i64,
+    pub minor: i64,
+    pub patch: i64,
+    pub api_level: i64,
+    /// Oldest api level nvim is still compatible with.
+    pub api_compatible: i64,
+    pub api_prerelease: bool,
+}
+
+impl ApiVersion {
+    /// If nvim has the api `level`, i.e. the bindings generated for `level`
+    /// can be used with it.
+    pub fn supports(&self, level: i64) -> bool {
+        self.api_compatible <= level && level <= self.api_level
+    }
+}
+
+impl std::fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
     }
 }
 
@@ -150,20 +393,111 @@ pub struct ModeInfo {
     pub name: Option<String>,
 }
 
-#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Default, serde::Serialize)]
 pub struct CmdlineContent {
     pub hl_id: i64,
     pub text: String,
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
-pub struct Window(rmpv::Value);
+impl_seq_deserialize!(CmdlineContent { hl_id, text });
 
-#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
-pub struct Buffer(rmpv::Value);
+/// Params of `win_viewport`.
+#[derive(Debug)]
+pub struct WinViewport {
+    pub grid: i64,
+    pub win: Window,
+    pub topline: i64,
+    pub botline: i64,
+    pub curline: i64,
+    pub curcol: i64,
+    pub line_count: i64,
+    /// How many (displayed) lines the topline moved since the previous
+    /// `win_viewport`. Not sent by neovim versions older than 0.10.
+    pub scroll_delta: Option<i64>,
+}
+
+impl_seq_deserialize!(WinViewport {
+    grid,
+    win,
+    topline,
+    botline,
+    curline,
+    curcol,
+    line_count;
+    scroll_delta
+});
+
+/// A highlighted chunk of a message.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct MsgContent {
+    pub hl_id: i64,
+    pub text: String,
+}
+
+impl_seq_deserialize!(MsgContent { hl_id, text });
+
+/// An entry of `msg_history_show`.
+#[derive(Debug, Default)]
+pub struct MsgHistoryEntry {
+    pub kind: String,
+    pub content: Vec<MsgContent>,
+}
+
+impl_seq_deserialize!(MsgHistoryEntry { kind, content });
 
-#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
-pub struct Tabpage(rmpv::Value);
+/// Implements a handle type, which is transferred as a msgpack EXT value. The
+/// ext type ids are generated from the api-info's `types`.
+macro_rules! ext_handle {
+    ($name:ident) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(i64);
+
+        impl $name {
+            pub fn new(handle: i64) -> Self {
+                Self(handle)
+            }
+
+            /// The handle's integer value.
+            pub fn handle(&self) -> i64 {
+                self.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.0)
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                let data = rmp_serde::to_vec(&self.0).map_err(serde::ser::Error::custom)?;
+                rmpv::Value::Ext(Self::EXT_ID, data).serialize(s)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                match rmpv::Value::deserialize(d)? {
+                    rmpv::Value::Ext(id, data) if id == Self::EXT_ID => {
+                        rmp_serde::from_slice::<i64>(&data)
+                            .map(Self)
+                            .map_err(serde::de::Error::custom)
+                    }
+                    v => Err(serde::de::Error::custom(format!(
+                        "invalid {} value: {:?}",
+                        stringify!($name),
+                        v
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+ext_handle!(Buffer);
+ext_handle!(Window);
+ext_handle!(Tabpage);
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct TablineTab {
@@ -177,7 +511,7 @@ pub struct TablineBuffer {
     pub buffer: Buffer,
 }
 
-#[derive(Default, Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Default, Debug, serde::Serialize)]
 pub struct PopupmenuItem {
     pub word: String,
     pub kind: String,
@@ -185,6 +519,13 @@ pub struct PopupmenuItem {
     pub info: String,
 }
 
+impl_seq_deserialize!(PopupmenuItem {
+    word,
+    kind,
+    menu,
+    info
+});
+
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(transparent)]
 pub struct Dictionary(rmpv::Value);
@@ -1,23 +1,85 @@
+pub mod bufevents;
 mod gen;
+pub mod keysets;
 mod manual;
 
+use serde::Deserialize;
+
 pub use manual::*;
 
 pub mod uievents {
     pub use super::gen::*;
+    pub use super::manual::WinViewport;
 }
 
 pub use uievents::UiEvent;
 
-pub fn decode_redraw_params(params: rmpv::Value) -> Result<Vec<UiEvent>, rmpv::ext::Error> {
+pub use bufevents::{decode_buf_event, BufEvent};
+
+/// Decodes the params of a `redraw` notification from an already decoded
+/// value. Prefer `Redraw` (or `decode_redraw`), which decodes the events
+/// straight from the message's bytes.
+pub fn decode_redraw_params(
+    params: rmpv::Value,
+) -> Result<Vec<UiEvent<'static>>, rmpv::ext::Error> {
     match params {
-        rmpv::Value::Array(params) => params
-            .into_iter()
-            .map(rmpv::ext::from_value::<uievents::UiEvent>)
-            .collect(),
+        rmpv::Value::Array(params) => params.into_iter().map(UiEvent::deserialize).collect(),
         params => Err(rmpv::ext::Error::Syntax(format!(
             "Invalid params type: {:?}",
             params
         ))),
     }
 }
+
+/// Decodes the msgpack encoded params of a `redraw` notification. The events
+/// borrow from `params` where possible (e.g. the cell text of `grid_line`).
+pub fn decode_redraw(params: &[u8]) -> Result<Vec<UiEvent<'_>>, rmp_serde::decode::Error> {
+    rmp_serde::from_slice(params)
+}
+
+/// Encoded `redraw` notification. The ui events are decoded on demand with
+/// `Redraw::events`, without going through an intermediate `rmpv::Value`.
+///
+/// The redraw owns a copy of the message, since it outlives the reader's
+/// buffer it was read into. Decoding is zero-copy only after that: the
+/// events borrow from the redraw.
+#[derive(Debug, Clone)]
+pub struct Redraw {
+    buf: Vec<u8>,
+    /// Offset of the params in `buf`.
+    params: usize,
+}
+
+impl Redraw {
+    /// Creates a new redraw from a single encoded msgpack-rpc message. If
+    /// the message is not a `redraw` notification, `None` is returned.
+    ///
+    /// The message is copied into the redraw.
+    pub fn from_message(msg: &[u8]) -> Option<Self> {
+        let params = msg.len() - redraw_params(msg)?.len();
+        Some(Self {
+            buf: msg.to_vec(),
+            params,
+        })
+    }
+
+    /// Decode the ui events.
+    pub fn events(&self) -> Result<Vec<UiEvent<'_>>, rmp_serde::decode::Error> {
+        decode_redraw(&self.buf[self.params..])
+    }
+}
+
+/// Returns the params of the message, if the message is a `redraw`
+/// notification.
+fn redraw_params(mut msg: &[u8]) -> Option<&[u8]> {
+    if rmp::decode::read_array_len(&mut msg).ok()? != 3
+        || rmp::decode::read_int::<u8, _>(&mut msg).ok()? != 2
+    {
+        return None;
+    }
+
+    match rmp::decode::read_str_from_slice(msg).ok()? {
+        ("redraw", params) => Some(params),
+        _ => None,
+    }
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use rmpv::Value;
+
+fn map(entries: Vec<(&str, Value)>) -> Value {
+    Value::Map(
+        entries
+            .into_iter()
+            .map(|(k, v)| (Value::from(k), v))
+            .collect(),
+    )
+}
+
+fn function(name: &str, since: i64, deprecated_since: Option<i64>) -> Value {
+    let mut entries = vec![
+        ("name", Value::from(name)),
+        ("since", Value::from(since)),
+        (
+            "parameters",
+            Value::Array(vec![Value::Array(vec![
+                Value::from("Buffer"),
+                Value::from("buffer"),
+            ])]),
+        ),
+        ("return_type", Value::from("Integer")),
+        ("method", Value::from(true)),
+    ];
+    if let Some(deprecated_since) = deprecated_since {
+        entries.push(("deprecated_since", Value::from(deprecated_since)));
+    }
+
+    map(entries)
+}
+
+fn api_info(functions: Vec<Value>) -> Vec<u8> {
+    let ext = |id: i64, prefix: &str| {
+        map(vec![
+            ("id", Value::from(id)),
+            ("prefix", Value::from(prefix)),
+        ])
+    };
+    let info = map(vec![
+        (
+            "version",
+            map(vec![
+                ("major", Value::from(0)),
+                ("minor", Value::from(8)),
+                ("patch", Value::from(0)),
+                ("api_level", Value::from(10)),
+                ("api_compatible", Value::from(0)),
+                ("api_prerelease", Value::from(false)),
+            ]),
+        ),
+        ("functions", Value::Array(functions)),
+        ("ui_events", Value::Array(vec![])),
+        ("ui_options", Value::Array(vec![])),
+        (
+            "types",
+            map(vec![
+                ("Buffer", ext(0, "nvim_buf_")),
+                ("Window", ext(1, "nvim_win_")),
+                ("Tabpage", ext(2, "nvim_tabpage_")),
+            ]),
+        ),
+        (
+            "error_types",
+            map(vec![
+                ("Exception", map(vec![("id", Value::from(0))])),
+                ("Validation", map(vec![("id", Value::from(1))])),
+            ]),
+        ),
+    ]);
+
+    let mut bytes = vec![];
+    rmpv::encode::write_value(&mut bytes, &info).unwrap();
+    bytes
+}
+
+fn apigen(args: &[&str], input: &[u8]) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_apigen"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn functions_keep_deprecated_bindings() {
+    let input = api_info(vec![
+        function("nvim_buf_get_name", 1, None),
+        function("nvim_buf_get_number", 1, Some(2)),
+        function("nvim_buf_get_future", 11, None),
+    ]);
+
+    let out = apigen(&["functions", "10"], &input);
+    assert!(out.contains("pub async fn nvim_buf_get_name"));
+    assert!(!out.contains("nvim_buf_get_future"));
+    // Deprecated before the target level, but still generated with the
+    // attribute for both the client function and the handle method.
+    assert_eq!(
+        out.matches("# [deprecated (note = \"deprecated since api level 2\")]")
+            .count(),
+        2
+    );
+    assert!(out.contains("pub async fn nvim_buf_get_number"));
+    assert!(out.contains("pub async fn get_number"));
+    assert!(out.contains("pub const MIN_API_LEVEL : i64 = 10i64"));
+
+    let out = apigen(&["functions", "1"], &input);
+    assert!(out.contains("pub async fn nvim_buf_get_number"));
+    assert!(out.contains("pub const MIN_API_LEVEL : i64 = 1i64"));
+}
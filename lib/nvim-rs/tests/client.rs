@@ -1,7 +1,12 @@
 use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 
+use nvim_rs::handler::error_value;
 use nvim_rs::rpc::{Message, RpcReader, RpcWriter};
-use nvim_rs::{args, Client};
+use nvim_rs::testing::{Expect, MockServer};
+use nvim_rs::types::{Buffer, Window};
+use nvim_rs::{
+    args, CallError, Client, HandleError, NvimError, NvimErrorKind, RequestHandlers, Session,
+};
 
 #[tokio::test]
 async fn void_response_decodes_correctly() {
@@ -36,7 +41,8 @@ async fn void_response_decodes_correctly() {
                 let writer = writer.compat_write();
                 let mut reader: RpcReader<_> = reader.compat().into();
 
-                let mut client = Client::new(writer);
+                let (client, writer_task) = Client::new(writer);
+                tokio::task::spawn(writer_task);
 
                 let res = client.call::<(), _, _>("get_nil", args![]).await.unwrap();
 
@@ -58,6 +64,476 @@ async fn void_response_decodes_correctly() {
         .await;
 }
 
+#[tokio::test]
+async fn error_response_decodes_correctly() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let local = tokio::task::LocalSet::new();
+
+    local
+        .run_until(async move {
+            let server_handle = tokio::task::spawn_local(async move {
+                let (reader, writer) = tokio::io::split(server);
+                let mut writer = writer.compat_write();
+                let mut reader: RpcReader<_> = reader.compat().into();
+
+                for error in [
+                    rmpv::Value::from(vec![
+                        rmpv::Value::from(1),
+                        rmpv::Value::from("Invalid buffer id: 9"),
+                    ]),
+                    rmpv::Value::from(true),
+                ] {
+                    let req = match reader.recv().await.unwrap() {
+                        Message::Request(req) => req,
+                        v => panic!("Unexpected message: {:?}", v),
+                    };
+
+                    writer
+                        .write_rpc_response(req.msgid, Some(&error), None::<&rmpv::Value>)
+                        .await
+                        .unwrap();
+                }
+            });
+
+            let client_handle = tokio::task::spawn_local(async move {
+                let (reader, writer) = tokio::io::split(client);
+                let writer = writer.compat_write();
+                let mut reader: RpcReader<_> = reader.compat().into();
+
+                let (client, writer_task) = Client::new(writer);
+                tokio::task::spawn(writer_task);
+
+                let res1 = client.call::<(), _, _>("fail", args![]).await.unwrap();
+                let res2 = client.call::<(), _, _>("fail", args![]).await.unwrap();
+
+                for _ in 0..2 {
+                    match reader.recv().await.unwrap() {
+                        Message::Response(response) => client.handle_response(response).unwrap(),
+                        v => panic!("unexpected message: {:?}", v),
+                    }
+                }
+
+                let err = res1.await.unwrap_err();
+                assert_eq!(
+                    err,
+                    CallError::Error(NvimError {
+                        kind: NvimErrorKind::Validation,
+                        message: "Invalid buffer id: 9".to_string(),
+                    })
+                );
+                assert_eq!(err.to_string(), "validation error: Invalid buffer id: 9");
+
+                assert_eq!(
+                    res2.await,
+                    Err(CallError::DecodeError(rmpv::Value::from(true)))
+                );
+            });
+
+            tokio::try_join!(server_handle, client_handle).unwrap();
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn requests_are_dispatched_to_handlers() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let local = tokio::task::LocalSet::new();
+
+    local
+        .run_until(async move {
+            let server_handle = tokio::task::spawn_local(async move {
+                let (reader, writer) = tokio::io::split(server);
+                let mut writer = writer.compat_write();
+                let mut reader: RpcReader<_> = reader.compat().into();
+
+                writer.write_rpc_request(1, "add", &(3, 5)).await.unwrap();
+                writer
+                    .write_rpc_request(2, "nonexistent", &rmpv::Value::Array(vec![]))
+                    .await
+                    .unwrap();
+
+                match reader.recv().await.unwrap() {
+                    Message::Response(res) => {
+                        assert_eq!(res.msgid, 1);
+                        assert_eq!(res.error, None);
+                        assert_eq!(res.result, Some(rmpv::Value::from(8)));
+                    }
+                    v => panic!("Unexpected message: {:?}", v),
+                }
+
+                match reader.recv().await.unwrap() {
+                    Message::Response(res) => {
+                        assert_eq!(res.msgid, 2);
+                        assert_eq!(res.result, None);
+                        assert_eq!(
+                            res.error,
+                            Some(rmpv::Value::from(vec![
+                                rmpv::Value::from(0),
+                                rmpv::Value::from("unknown request method: nonexistent"),
+                            ]))
+                        );
+                    }
+                    v => panic!("Unexpected message: {:?}", v),
+                }
+            });
+
+            let client_handle = tokio::task::spawn_local(async move {
+                let (reader, writer) = tokio::io::split(client);
+                let writer = writer.compat_write();
+                let mut reader: RpcReader<_> = reader.compat().into();
+
+                let (client, writer_task) = Client::new(writer);
+                tokio::task::spawn(writer_task);
+                let mut handlers = RequestHandlers::new();
+                handlers.register("add", |params: rmpv::Value| {
+                    let (a, b): (i64, i64) = rmpv::ext::from_value(params)
+                        .map_err(|err| error_value(err.to_string()))?;
+                    Ok(rmpv::Value::from(a + b))
+                });
+
+                for _ in 0..2 {
+                    match reader.recv().await.unwrap() {
+                        Message::Request(req) => {
+                            let msgid = req.msgid;
+                            let res = handlers.handle(req).await;
+                            client.respond(msgid, res).await.unwrap();
+                        }
+                        v => panic!("unexpected message: {:?}", v),
+                    }
+                }
+            });
+
+            tokio::try_join!(server_handle, client_handle).unwrap();
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn concurrent_callers_get_their_own_responses() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let server = async move {
+        let (reader, writer) = tokio::io::split(server);
+        let mut writer = writer.compat_write();
+        let mut reader: RpcReader<_> = reader.compat().into();
+
+        let mut reqs = vec![];
+        for _ in 0..10 {
+            match reader.recv().await.unwrap() {
+                Message::Request(req) => reqs.push(req),
+                v => panic!("Unexpected message: {:?}", v),
+            }
+        }
+
+        // Respond in reverse order.
+        for req in reqs.into_iter().rev() {
+            writer
+                .write_rpc_response(req.msgid, None::<&rmpv::Value>, Some(&req.params[0]))
+                .await
+                .unwrap();
+        }
+    };
+
+    let (reader, writer) = tokio::io::split(client);
+    let writer = writer.compat_write();
+    let mut reader: RpcReader<_> = reader.compat().into();
+
+    let (client, writer_task) = Client::new(writer);
+    tokio::task::spawn(writer_task);
+
+    let reader_client = client.clone();
+    tokio::task::spawn(async move {
+        loop {
+            match reader.recv().await {
+                Ok(Message::Response(response)) => reader_client.handle_response(response).unwrap(),
+                Ok(v) => panic!("unexpected message: {:?}", v),
+                Err(_) => break,
+            }
+        }
+    });
+
+    let calls = (0..10).map(|i| {
+        let client = client.clone();
+        tokio::task::spawn(async move {
+            let res = client.call::<i64, _, _>("echo", args![i]).await.unwrap();
+            assert_eq!(res.await, Ok(i));
+        })
+    });
+
+    let (calls, _) = tokio::join!(futures::future::join_all(calls), server);
+    for call in calls {
+        call.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn dropped_caller_removes_its_callback() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let (reader, writer) = tokio::io::split(server);
+    let mut writer = writer.compat_write();
+    let mut server_reader: RpcReader<_> = reader.compat().into();
+
+    let (reader, client_writer) = tokio::io::split(client);
+    let mut reader: RpcReader<_> = reader.compat().into();
+
+    let (client, writer_task) = Client::new(client_writer.compat_write());
+    tokio::task::spawn(writer_task);
+
+    let res = client.call::<(), _, _>("get_nil", args![]).await.unwrap();
+    drop(res);
+
+    let req = match server_reader.recv().await.unwrap() {
+        Message::Request(req) => req,
+        v => panic!("Unexpected message: {:?}", v),
+    };
+    writer
+        .write_rpc_response(req.msgid, None::<&rmpv::Value>, None::<&rmpv::Value>)
+        .await
+        .unwrap();
+
+    match reader.recv().await.unwrap() {
+        Message::Response(response) => assert!(matches!(
+            client.handle_response(response),
+            Err(HandleError::CallerMissing(_))
+        )),
+        v => panic!("unexpected message: {:?}", v),
+    }
+}
+
+#[tokio::test]
+async fn atomic_batch_decodes_results() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let server = async move {
+        let (reader, writer) = tokio::io::split(server);
+        let mut writer = writer.compat_write();
+        let mut reader: RpcReader<_> = reader.compat().into();
+
+        let req = match reader.recv().await.unwrap() {
+            Message::Request(req) => req,
+            v => panic!("Unexpected message: {:?}", v),
+        };
+
+        assert_eq!(req.method, "nvim_call_atomic");
+        assert_eq!(
+            req.params,
+            rmpv::Value::from(vec![rmpv::Value::from(vec![
+                rmpv::Value::from(vec![
+                    rmpv::Value::from("echo"),
+                    rmpv::Value::from(vec![rmpv::Value::from(3)]),
+                ]),
+                rmpv::Value::from(vec![
+                    rmpv::Value::from("nothing"),
+                    rmpv::Value::Array(vec![]),
+                ]),
+                rmpv::Value::from(vec![
+                    rmpv::Value::from("echo"),
+                    rmpv::Value::from(vec![rmpv::Value::from("foo")]),
+                ]),
+            ])])
+        );
+
+        let result = rmpv::Value::from(vec![
+            rmpv::Value::from(vec![
+                rmpv::Value::from(3),
+                rmpv::Value::Nil,
+                rmpv::Value::from("foo"),
+            ]),
+            rmpv::Value::Nil,
+        ]);
+        writer
+            .write_rpc_response(req.msgid, None::<&rmpv::Value>, Some(&result))
+            .await
+            .unwrap();
+    };
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+    tokio::task::spawn(Session::new(reader.compat(), client.clone()).run());
+
+    let res = client
+        .batch()
+        .call::<i64, _, _>("echo", args![3])
+        .call::<(), _, _>("nothing", rmpv::Value::Array(vec![]))
+        .call::<String, _, _>("echo", args!["foo"])
+        .atomic()
+        .await
+        .unwrap();
+
+    let (res, _) = tokio::join!(res, server);
+    assert_eq!(res, Ok((3, (), "foo".to_string())));
+}
+
+#[tokio::test]
+async fn atomic_batch_reports_failed_call() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let server = async move {
+        let (reader, writer) = tokio::io::split(server);
+        let mut writer = writer.compat_write();
+        let mut reader: RpcReader<_> = reader.compat().into();
+
+        let req = match reader.recv().await.unwrap() {
+            Message::Request(req) => req,
+            v => panic!("Unexpected message: {:?}", v),
+        };
+
+        let result = rmpv::Value::from(vec![
+            rmpv::Value::from(vec![rmpv::Value::from(3)]),
+            rmpv::Value::from(vec![
+                rmpv::Value::from(1),
+                rmpv::Value::from(1),
+                rmpv::Value::from("Invalid buffer id: 9"),
+            ]),
+        ]);
+        writer
+            .write_rpc_response(req.msgid, None::<&rmpv::Value>, Some(&result))
+            .await
+            .unwrap();
+    };
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+    tokio::task::spawn(Session::new(reader.compat(), client.clone()).run());
+
+    let res = client
+        .batch()
+        .call::<i64, _, _>("echo", args![3])
+        .call::<(), _, _>("nvim_buf_delete", args![9])
+        .atomic()
+        .await
+        .unwrap();
+
+    let (res, _) = tokio::join!(res, server);
+    let err = res.unwrap_err();
+    assert_eq!(
+        err,
+        CallError::Batch {
+            index: 1,
+            error: Box::new(CallError::Error(NvimError {
+                kind: NvimErrorKind::Validation,
+                message: "Invalid buffer id: 9".to_string(),
+            })),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "call 1 of batch failed: validation error: Invalid buffer id: 9"
+    );
+}
+
+#[tokio::test]
+async fn pipelined_batch_writes_all_requests_before_awaiting() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let server = async move {
+        let (reader, writer) = tokio::io::split(server);
+        let mut writer = writer.compat_write();
+        let mut reader: RpcReader<_> = reader.compat().into();
+
+        // NOTE: Nothing is responded until all the requests are
+        // received, so this would deadlock if the client waited for the
+        // responses one by one.
+        let mut reqs = vec![];
+        for _ in 0..3 {
+            match reader.recv().await.unwrap() {
+                Message::Request(req) => reqs.push(req),
+                v => panic!("Unexpected message: {:?}", v),
+            }
+        }
+
+        for req in reqs {
+            match req.method.as_str() {
+                "echo" => writer
+                    .write_rpc_response(req.msgid, None::<&rmpv::Value>, Some(&req.params[0]))
+                    .await
+                    .unwrap(),
+                "nothing" => writer
+                    .write_rpc_response(req.msgid, None::<&rmpv::Value>, None::<&rmpv::Value>)
+                    .await
+                    .unwrap(),
+                _ => writer
+                    .write_rpc_response(
+                        req.msgid,
+                        Some(&error_value("no such method")),
+                        None::<&rmpv::Value>,
+                    )
+                    .await
+                    .unwrap(),
+            }
+        }
+    };
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+    tokio::task::spawn(Session::new(reader.compat(), client.clone()).run());
+
+    let ok = client
+        .batch()
+        .call::<i64, _, _>("echo", args![1])
+        .call::<(), _, _>("nothing", rmpv::Value::Array(vec![]))
+        .pipelined()
+        .await
+        .unwrap();
+    let failed = client
+        .batch()
+        .call::<(), _, _>("missing", rmpv::Value::Array(vec![]))
+        .pipelined()
+        .await
+        .unwrap();
+
+    let (ok, failed, _) = tokio::join!(ok, failed, server);
+    assert_eq!(ok, Ok((1, ())));
+    assert_eq!(
+        failed,
+        Err(CallError::Batch {
+            index: 0,
+            error: Box::new(CallError::Error(NvimError {
+                kind: NvimErrorKind::Exception,
+                message: "no such method".to_string(),
+            })),
+        })
+    );
+}
+
+#[tokio::test]
+async fn handle_methods_pass_the_handle_as_the_first_arg() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+    tokio::task::spawn(Session::new(reader.compat(), client.clone()).run());
+
+    let buffer = Buffer::new(3);
+    let window = Window::new(1000);
+
+    let mut server = MockServer::new(server.compat());
+    server
+        .expect(
+            Expect::request("nvim_buf_get_lines")
+                .params(args![buffer, 0, -1, false])
+                .returns(["foo", "bar"]),
+        )
+        .expect(Expect::request("nvim_win_set_cursor").params(args![window, (1, 0)]));
+    let server = tokio::task::spawn(server.run());
+
+    let lines = buffer.get_lines(&client, 0, -1, false).await.unwrap();
+    assert_eq!(lines.await, Ok(vec!["foo".to_string(), "bar".to_string()]));
+
+    let res = window.set_cursor(&client, (1, 0)).await.unwrap();
+    assert_eq!(res.await, Ok(()));
+
+    drop(client);
+    server.await.unwrap().unwrap();
+}
+
 #[test]
 fn args_macro() {
     let args = args!(3, 5, "foobar".to_string());
@@ -1,41 +1,44 @@
 use std::process::Stdio;
 use std::time::Duration;
 
+use futures::StreamExt;
 use nvim_rs::types::{Object, UiOptions};
 use tokio::process::Command;
 use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 
-use nvim_rs::rpc::{Message, RpcReader};
-use nvim_rs::Client;
+use nvim_rs::{Client, Session};
 
-#[tokio::test]
-async fn smoke_test() {
+fn spawn_nvim() -> (Client, Session<impl futures::AsyncRead + Unpin>) {
     let mut cmd = Command::new("nvim")
         .arg("--headless")
         .arg("--cmd")
         .arg("call stdioopen({'rpc': v:true})")
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
+        .kill_on_drop(true)
         .spawn()
         .unwrap();
 
     let stdin = cmd.stdin.take().unwrap();
     let stdout = cmd.stdout.take().unwrap();
 
-    let writer = stdin.compat_write();
-    let mut reader: RpcReader<_> = stdout.compat().into();
+    let (client, writer_task) = Client::new(stdin.compat_write());
+    tokio::task::spawn(writer_task);
 
-    let mut client = Client::new(writer);
+    // NOTE: Keep the process alive for the duration of the test.
+    tokio::task::spawn(async move { cmd.wait().await });
 
-    let result = client.nvim_get_vvar("argv".into()).await.unwrap();
+    let session = Session::new(stdout.compat(), client.clone());
 
-    let handle = tokio::task::spawn(async move {
-        let v = reader.recv().await.unwrap();
-        match v {
-            Message::Response(response) => client.handle_response(response).unwrap(),
-            v => panic!("unexpected message: {:?}", v),
-        }
-    });
+    (client, session)
+}
+
+#[tokio::test]
+async fn smoke_test() {
+    let (client, session) = spawn_nvim();
+    tokio::task::spawn(session.run());
+
+    let result = client.nvim_get_vvar("argv").await.unwrap();
 
     let vals = vec![
         rmpv::Value::from("nvim"),
@@ -45,61 +48,44 @@ async fn smoke_test() {
     ];
 
     assert_eq!(result.await, Ok(Object::new(vals)));
-
-    handle.await.unwrap();
 }
 
 #[tokio::test]
 async fn smoke_test_ui_attach() {
     // Smoke test for deserializing _some_ of the UI events.
+    let (client, mut session) = spawn_nvim();
+    let mut redraw = session.redraw();
+    tokio::task::spawn(session.run());
 
-    let mut cmd = Command::new("nvim")
-        .arg("--headless")
-        .arg("--cmd")
-        .arg("call stdioopen({'rpc': v:true})")
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
+    let res = client
+        .nvim_ui_attach(10, 10, UiOptions::default())
+        .await
         .unwrap();
 
-    let stdin = cmd.stdin.take().unwrap();
-    let stdout = cmd.stdout.take().unwrap();
+    res.await.unwrap();
 
-    let writer = stdin.compat_write();
-    let mut reader: RpcReader<_> = stdout.compat().into();
+    // Read what ever redraw events we manage get in a reasonalbe time.
+    let read = async {
+        while let Some(redraw) = redraw.next().await {
+            redraw.events().unwrap();
+        }
+    };
+
+    let _ = tokio::time::timeout(Duration::from_secs(2), read).await;
+}
 
-    let mut client = Client::new(writer);
+#[tokio::test]
+async fn requests_without_subscriber_get_an_error() {
+    let (client, session) = spawn_nvim();
+    tokio::task::spawn(session.run());
 
     let res = client
-        .nvim_ui_attach(10, 10, UiOptions::default())
+        .nvim_exec_lua(
+            "return vim.rpcrequest(vim.api.nvim_get_api_info()[1], 'foobar')",
+            vec![],
+        )
         .await
         .unwrap();
 
-    let handle = tokio::spawn(async move { res.await.unwrap() });
-
-    // Read what ever messages we manage get in a reasonalbe time and deserialize them.
-    loop {
-        tokio::select! {
-            _sleep = tokio::time::sleep(Duration::from_secs(2)) => {
-                println!("Timeout");
-                break;
-            },
-            v = reader.recv() => {
-                match v.unwrap() {
-                    Message::Response(response) => client.handle_response(response).unwrap(),
-                    Message::Notification(notification) => {
-                        match notification.method.as_ref() {
-                            "redraw" => {
-                                nvim_rs::types::decode_redraw_params(notification.params).unwrap();
-                            }
-                            _ => panic!("unexpected notification: {}", notification.method),
-                        }
-                    }
-                    v => panic!("unexpected message: {:?}", v),
-                }
-            },
-        }
-    }
-
-    handle.await.unwrap();
+    assert!(res.await.is_err());
 }
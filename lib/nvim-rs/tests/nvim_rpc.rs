@@ -0,0 +1,76 @@
+#![cfg(unix)]
+
+use tokio_util::compat::TokioAsyncReadCompatExt;
+
+use nvim_rs::args;
+use nvim_rs::testing::{Expect, MockServer};
+
+/// Runs nvim-rpc with `args` against a mock server playing `script`, and
+/// returns what nvim-rpc printed.
+async fn nvim_rpc(args: &[&str], script: Vec<Expect>) -> String {
+    let dir = std::env::temp_dir().join(format!("nvim-rpc-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let socket = dir.join(format!("{}.sock", args.join("-")));
+    let _ = std::fs::remove_file(&socket);
+    let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+
+    let child = tokio::process::Command::new(env!("CARGO_BIN_EXE_nvim-rpc"))
+        .arg("--server")
+        .arg(&socket)
+        .args(args)
+        .output();
+
+    let server = async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = MockServer::new(stream.compat());
+        for expect in script {
+            server.expect(expect);
+        }
+        server.run().await
+    };
+
+    let server = tokio::task::spawn(server);
+
+    let output = child.await.unwrap();
+    let _ = std::fs::remove_file(&socket);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    server.await.unwrap().unwrap();
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[tokio::test]
+async fn call_prints_json() {
+    let out = nvim_rpc(
+        &["call", "nvim_buf_get_lines", "0", "0", "-1", "false"],
+        vec![Expect::request("nvim_buf_get_lines")
+            .params(args![0, 0, -1, false])
+            .returns(vec!["a", "b"])],
+    )
+    .await;
+    assert_eq!(out, "[\"a\",\"b\"]\n");
+
+    // Non-JSON args are strings, and nil results are printed as null.
+    let out = nvim_rpc(
+        &["call", "nvim_command", "echo 1"],
+        vec![Expect::request("nvim_command").params(args!["echo 1"])],
+    )
+    .await;
+    assert_eq!(out, "null\n");
+}
+
+#[tokio::test]
+async fn send_inputs_keys() {
+    let out = nvim_rpc(
+        &["send", "ihello<Esc>"],
+        vec![Expect::request("nvim_input")
+            .params(args!["ihello<Esc>"])
+            .returns(10)],
+    )
+    .await;
+    assert_eq!(out, "");
+}
@@ -0,0 +1,191 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::prelude::*;
+
+use nvim_rs::rpc::{Message, ReadError, RpcReader};
+
+/// Reader that returns its data in chunks of at most `chunk` bytes.
+struct Chunked {
+    data: Vec<u8>,
+    pos: usize,
+    chunk: usize,
+}
+
+impl Chunked {
+    fn new(data: Vec<u8>, chunk: usize) -> Self {
+        Self {
+            data,
+            pos: 0,
+            chunk,
+        }
+    }
+}
+
+impl AsyncRead for Chunked {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let n = buf.len().min(self.chunk).min(self.data.len() - self.pos);
+        buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+fn encode(value: &rmpv::Value) -> Vec<u8> {
+    let mut buf = vec![];
+    rmpv::encode::write_value(&mut buf, value).unwrap();
+    buf
+}
+
+fn notification(method: &str, params: rmpv::Value) -> rmpv::Value {
+    rmpv::Value::Array(vec![2.into(), method.into(), params])
+}
+
+/// Params with (at least) one value of each msgpack type.
+fn params(len: usize) -> rmpv::Value {
+    rmpv::Value::Array(vec![
+        rmpv::Value::Nil,
+        true.into(),
+        false.into(),
+        1.into(),
+        (-1).into(),
+        200.into(),
+        (-100).into(),
+        60_000.into(),
+        (-30_000).into(),
+        4_000_000_000u64.into(),
+        (-2_000_000_000).into(),
+        u64::MAX.into(),
+        i64::MIN.into(),
+        rmpv::Value::F32(1.5),
+        rmpv::Value::F64(-2.25),
+        "foo".into(),
+        "x".repeat(40).into(),
+        "x".repeat(300).into(),
+        "x".repeat(len).into(),
+        rmpv::Value::Binary(vec![1; 10]),
+        rmpv::Value::Binary(vec![2; 300]),
+        rmpv::Value::Binary(vec![3; len]),
+        rmpv::Value::Ext(1, vec![3]),
+        rmpv::Value::Ext(1, vec![3; 2]),
+        rmpv::Value::Ext(1, vec![3; 4]),
+        rmpv::Value::Ext(1, vec![3; 8]),
+        rmpv::Value::Ext(1, vec![3; 16]),
+        rmpv::Value::Ext(1, vec![3; 3]),
+        rmpv::Value::Ext(1, vec![3; 300]),
+        rmpv::Value::Ext(1, vec![3; len]),
+        rmpv::Value::Map(vec![("a".into(), rmpv::Value::Array(vec![]))]),
+        rmpv::Value::Map(
+            (0..20)
+                .map(|i| (i.into(), rmpv::Value::Array(vec![i.into()])))
+                .collect(),
+        ),
+        rmpv::Value::Array((0..20).map(rmpv::Value::from).collect()),
+        rmpv::Value::Array(vec![rmpv::Value::Array(vec![
+            rmpv::Value::Array(vec![]),
+            rmpv::Value::Map(vec![]),
+        ])]),
+    ])
+}
+
+async fn recv_notification<R>(reader: &mut RpcReader<R>) -> (String, rmpv::Value)
+where
+    R: AsyncRead + Unpin,
+{
+    match reader.recv().await.unwrap() {
+        Message::Notification(notification) => (notification.method, notification.params),
+        msg => panic!("unexpected message: {:?}", msg),
+    }
+}
+
+#[tokio::test]
+async fn message_in_single_byte_chunks() {
+    let params = params(10);
+    let data = [
+        encode(&notification("foo", params.clone())),
+        encode(&notification("bar", rmpv::Value::Array(vec![]))),
+    ]
+    .concat();
+
+    let mut reader = RpcReader::new(Chunked::new(data, 1));
+
+    assert_eq!(
+        recv_notification(&mut reader).await,
+        ("foo".to_string(), params)
+    );
+    assert_eq!(
+        recv_notification(&mut reader).await,
+        ("bar".to_string(), rmpv::Value::Array(vec![]))
+    );
+}
+
+#[tokio::test]
+async fn multiple_messages_in_one_chunk() {
+    let data = (0..10)
+        .map(|i| {
+            encode(&notification(
+                &format!("msg{}", i),
+                rmpv::Value::Array(vec![i.into()]),
+            ))
+        })
+        .collect::<Vec<_>>();
+    let len = data.iter().map(Vec::len).sum();
+
+    let mut reader = RpcReader::new(Chunked::new(data.concat(), len));
+
+    // Check the framing of half of the messages, and decode the rest.
+    for expected in &data[..5] {
+        assert_eq!(reader.recv_raw().await.unwrap(), expected.as_slice());
+    }
+
+    for i in 5..10 {
+        assert_eq!(
+            recv_notification(&mut reader).await,
+            (
+                format!("msg{}", i),
+                rmpv::Value::Array(vec![rmpv::Value::from(i)])
+            )
+        );
+    }
+
+    assert!(matches!(
+        reader.recv().await,
+        Err(ReadError::IOError(err)) if err.kind() == io::ErrorKind::UnexpectedEof
+    ));
+}
+
+#[tokio::test]
+async fn large_message_across_reads() {
+    // Has 16 and 32 bit lengths, and doesn't fit into a single read.
+    let params = params(70_000);
+    let data = [
+        encode(&notification("foo", params.clone())),
+        encode(&notification("bar", rmpv::Value::Array(vec![]))),
+    ]
+    .concat();
+
+    let mut reader = RpcReader::new(Chunked::new(data, 1000));
+
+    assert_eq!(
+        recv_notification(&mut reader).await,
+        ("foo".to_string(), params)
+    );
+    assert_eq!(
+        recv_notification(&mut reader).await,
+        ("bar".to_string(), rmpv::Value::Array(vec![]))
+    );
+}
+
+#[tokio::test]
+async fn invalid_marker_is_an_error() {
+    let mut reader = RpcReader::new(Chunked::new(vec![0x93, 0x02, 0xc1], 10));
+    assert!(matches!(
+        reader.recv_raw().await,
+        Err(ReadError::IOError(err)) if err.kind() == io::ErrorKind::InvalidData
+    ));
+}
@@ -0,0 +1,116 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+
+use nvim_rs::rpc::message::{Notification, Response};
+use nvim_rs::rpc::record::{Direction, Recorder, Replay};
+use nvim_rs::rpc::{Message, RpcReader, RpcWriter};
+use nvim_rs::{args, Client, Session};
+
+/// Writer that can be inspected after the recording.
+#[derive(Clone, Default)]
+struct Shared(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn session_and_client_traffic_is_recorded() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let buf = Shared::default();
+    let recorder = Recorder::new(buf.clone());
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+    client.record(recorder.clone());
+
+    let mut session = Session::new(reader.compat(), client.clone());
+    session.record(recorder.clone());
+    let session = tokio::task::spawn(session.run());
+
+    let (reader, writer) = tokio::io::split(server);
+    let mut writer = writer.compat_write();
+    let mut reader: RpcReader<_> = reader.compat().into();
+
+    let res = client.call::<i64, _, _>("get", args![1]).await.unwrap();
+    let req = match reader.recv().await.unwrap() {
+        Message::Request(req) => req,
+        v => panic!("Unexpected message: {:?}", v),
+    };
+    writer
+        .write_rpc_response(req.msgid, None::<&rmpv::Value>, Some(&7))
+        .await
+        .unwrap();
+    assert_eq!(res.await, Ok(7));
+
+    writer.write_rpc_notification("foo", &(1, 2)).await.unwrap();
+
+    drop(writer);
+    drop(reader);
+    assert!(session.await.unwrap().is_err());
+    assert!(recorder.take_error().is_none());
+
+    let data = buf.0.lock().unwrap().clone();
+    let entries = Replay::new(data.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert!(entries.windows(2).all(|w| w[0].time <= w[1].time));
+    match entries.as_slice() {
+        [req, res, notification] => {
+            assert_eq!(req.direction, Direction::Outgoing);
+            assert!(matches!(
+                &req.message,
+                Message::Request(req) if req.method == "get"
+                    && req.params == rmpv::Value::Array(vec![1.into()])
+            ));
+
+            assert_eq!(res.direction, Direction::Incoming);
+            assert!(matches!(
+                &res.message,
+                Message::Response(res) if res.result == Some(7.into()) && res.error.is_none()
+            ));
+
+            assert_eq!(notification.direction, Direction::Incoming);
+            assert!(matches!(
+                &notification.message,
+                Message::Notification(n) if n.method == "foo"
+            ));
+        }
+        entries => panic!("unexpected entries: {:?}", entries),
+    }
+}
+
+#[test]
+fn messages_encode_to_the_wire_format() {
+    let msg = Message::Notification(Notification::new("foo", rmpv::Value::Array(vec![1.into()])));
+    let wire = rmpv::Value::Array(vec![
+        2.into(),
+        "foo".into(),
+        rmpv::Value::Array(vec![1.into()]),
+    ]);
+
+    let mut expected = vec![];
+    rmpv::encode::write_value(&mut expected, &wire).unwrap();
+    assert_eq!(msg.to_vec().unwrap(), expected);
+
+    let msg = Message::Response(Response::new(3, None, Some("ok".into())));
+    match rmp_serde::from_slice(&msg.to_vec().unwrap()).unwrap() {
+        Message::Response(res) => {
+            assert_eq!(res.msgid, 3);
+            assert_eq!(res.error, None);
+            assert_eq!(res.result, Some("ok".into()));
+        }
+        msg => panic!("unexpected message: {:?}", msg),
+    }
+}
@@ -0,0 +1,168 @@
+use nvim_rs::screen::{Cursor, Placement, Screen};
+use nvim_rs::testing::ui_event;
+use nvim_rs::types::{decode_redraw_params, Window};
+
+fn apply(screen: &mut Screen, events: Vec<rmpv::Value>) {
+    let events = decode_redraw_params(rmpv::Value::Array(events)).unwrap();
+    screen.apply_all(events);
+}
+
+#[test]
+fn grid_line_sets_text_and_highlights() {
+    let mut screen = Screen::default();
+    apply(
+        &mut screen,
+        vec![
+            ui_event("grid_resize", [(1, 6, 2)]),
+            ui_event(
+                "grid_line",
+                [
+                    (1, 0, 0, rmpv::Value::from(vec![cell("a", Some(1), None)])),
+                    (
+                        1,
+                        1,
+                        1,
+                        rmpv::Value::from(vec![
+                            cell("b", Some(2), Some(2)),
+                            cell("c", None, None),
+                            cell("語", Some(3), None),
+                            cell("", None, None),
+                        ]),
+                    ),
+                ],
+            ),
+            ui_event("grid_cursor_goto", [(1, 1, 3)]),
+        ],
+    );
+
+    let grid = screen.grid(1).unwrap();
+    assert_eq!((grid.width(), grid.height()), (6, 2));
+    assert_eq!(grid.row_text(0), "a     ");
+    assert_eq!(grid.row_text(1), " bbc語");
+    assert_eq!(grid.row_hl_ids(1), vec![0, 2, 2, 2, 3, 3]);
+    assert_eq!(grid.text(), "a\n bbc語");
+    assert_eq!(
+        screen.cursor(),
+        Cursor {
+            grid: 1,
+            row: 1,
+            col: 3
+        }
+    );
+
+    apply(&mut screen, vec![ui_event("grid_clear", [(1,)])]);
+    assert_eq!(screen.grid(1).unwrap().text(), "\n");
+}
+
+#[test]
+fn grid_scroll_moves_the_region() {
+    let mut screen = Screen::default();
+    let rows = (0..4)
+        .map(|row| {
+            (
+                1,
+                row,
+                0,
+                rmpv::Value::from(vec![cell(&row.to_string(), Some(0), Some(3))]),
+            )
+        })
+        .collect::<Vec<_>>();
+    apply(
+        &mut screen,
+        vec![
+            ui_event("grid_resize", [(1, 3, 4)]),
+            ui_event("grid_line", rows.clone()),
+        ],
+    );
+
+    // Scroll rows 1..4 up by one, leaving the last column in place.
+    apply(
+        &mut screen,
+        vec![ui_event("grid_scroll", [(1, 1, 4, 0, 2, 1, 0)])],
+    );
+    assert_eq!(screen.grid(1).unwrap().text(), "000\n221\n332\n333");
+
+    // And back down by two, over the whole grid.
+    apply(
+        &mut screen,
+        vec![
+            ui_event("grid_line", rows),
+            ui_event("grid_scroll", [(1, 0, 4, 0, 3, -2, 0)]),
+        ],
+    );
+    assert_eq!(screen.grid(1).unwrap().text(), "000\n111\n000\n111");
+}
+
+#[test]
+fn windows_are_placed_on_grids() {
+    let mut screen = Screen::default();
+    apply(
+        &mut screen,
+        vec![
+            ui_event("grid_resize", [(1, 80, 24), (2, 80, 22), (3, 10, 2)]),
+            ui_event("win_pos", [(2, Window::new(1000), 0, 0, 80, 22)]),
+            ui_event(
+                "win_float_pos",
+                [(3, Window::new(1001), "NW", 2, 1.0, 2.0, true, 50)],
+            ),
+            ui_event("msg_set_pos", [(4, 23, false, "")]),
+            ui_event(
+                "hl_attr_define",
+                [(
+                    1,
+                    rmpv::Value::Map(vec![("bold".into(), true.into())]),
+                    rmpv::Value::Map(vec![]),
+                    rmpv::Value::Array(vec![]),
+                )],
+            ),
+        ],
+    );
+
+    let (id, grid) = screen.window_grid(Window::new(1000)).unwrap();
+    assert_eq!(id, 2);
+    assert_eq!(
+        grid.placement,
+        Some(Placement::Normal {
+            startrow: 0,
+            startcol: 0,
+            width: 80,
+            height: 22
+        })
+    );
+    assert!(matches!(
+        screen.grid(3).unwrap().placement,
+        Some(Placement::Float {
+            anchor_grid: 2,
+            zindex: 50,
+            ..
+        })
+    ));
+    assert_eq!(
+        screen.grid(4).unwrap().placement,
+        Some(Placement::Message {
+            row: 23,
+            scrolled: false
+        })
+    );
+    assert_eq!(screen.hl_attr(1).unwrap().bold, Some(true));
+
+    apply(
+        &mut screen,
+        vec![
+            ui_event("win_hide", [(2,)]),
+            ui_event("win_close", [(3,)]),
+            ui_event("grid_destroy", [(3,)]),
+        ],
+    );
+    assert!(screen.grid(2).unwrap().hidden);
+    assert!(screen.grid(3).is_none());
+    assert!(screen.window_grid(Window::new(1001)).is_none());
+}
+
+/// Encodes a `grid_line` cell. `repeat` requires `hl_id`.
+fn cell(text: &str, hl_id: Option<i64>, repeat: Option<i64>) -> rmpv::Value {
+    let mut cell = vec![rmpv::Value::from(text)];
+    cell.extend(hl_id.map(rmpv::Value::from));
+    cell.extend(repeat.map(rmpv::Value::from));
+    rmpv::Value::Array(cell)
+}
@@ -0,0 +1,150 @@
+use futures::StreamExt;
+use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+
+use nvim_rs::rpc::{Message, RpcReader, RpcWriter};
+use nvim_rs::types::UiEvent;
+use nvim_rs::{args, Client, Incoming, Session};
+
+#[tokio::test]
+async fn session_dispatches_messages() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+
+    let mut session = Session::new(reader.compat(), client.clone());
+    let mut redraw = session.redraw();
+    let mut foo = session.notifications("foo");
+    let mut requests = session.requests();
+    let session = tokio::task::spawn(session.run());
+
+    let (reader, writer) = tokio::io::split(server);
+    let mut writer = writer.compat_write();
+    let mut reader: RpcReader<_> = reader.compat().into();
+
+    let res = client.call::<i64, _, _>("get", args![]).await.unwrap();
+    let req = match reader.recv().await.unwrap() {
+        Message::Request(req) => req,
+        v => panic!("Unexpected message: {:?}", v),
+    };
+    writer
+        .write_rpc_response(req.msgid, None::<&rmpv::Value>, Some(&7))
+        .await
+        .unwrap();
+    assert_eq!(res.await, Ok(7));
+
+    writer
+        .write_rpc_notification(
+            "redraw",
+            &vec![vec![rmpv::Value::from("flush"), rmpv::Value::Array(vec![])]],
+        )
+        .await
+        .unwrap();
+    writer.write_rpc_notification("foo", &(1, 2)).await.unwrap();
+    writer
+        .write_rpc_notification("bar", &args![])
+        .await
+        .unwrap();
+    writer.write_rpc_request(100, "req", &(3,)).await.unwrap();
+
+    let batch = redraw.next().await.unwrap();
+    let events = batch.events().unwrap();
+    assert!(matches!(events.as_slice(), [UiEvent::Flush]));
+
+    assert_eq!(
+        foo.next().await,
+        Some(rmpv::Value::from(vec![
+            rmpv::Value::from(1),
+            rmpv::Value::from(2)
+        ]))
+    );
+
+    let req = requests.next().await.unwrap();
+    assert_eq!(req.msgid, 100);
+    assert_eq!(req.method, "req");
+
+    // Closing the connection ends the session.
+    drop(writer);
+    drop(reader);
+    assert!(session.await.unwrap().is_err());
+    assert_eq!(redraw.next().await.map(|_| ()), None);
+}
+
+#[tokio::test]
+async fn session_responds_to_unhandled_requests() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+    tokio::task::spawn(Session::new(reader.compat(), client).run());
+
+    let (reader, writer) = tokio::io::split(server);
+    let mut writer = writer.compat_write();
+    let mut reader: RpcReader<_> = reader.compat().into();
+
+    writer.write_rpc_request(5, "req", &args![]).await.unwrap();
+
+    match reader.recv().await.unwrap() {
+        Message::Response(res) => {
+            assert_eq!(res.msgid, 5);
+            assert!(res.error.is_some());
+        }
+        v => panic!("Unexpected message: {:?}", v),
+    }
+}
+
+#[tokio::test]
+async fn session_keeps_incoming_messages_in_order() {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+
+    let mut session = Session::new(reader.compat(), client);
+    let mut foo = session.notifications("foo");
+    let mut incoming = session.incoming();
+    tokio::task::spawn(session.run());
+
+    let (_reader, writer) = tokio::io::split(server);
+    let mut writer = writer.compat_write();
+
+    writer.write_rpc_request(1, "req", &args![]).await.unwrap();
+    writer
+        .write_rpc_notification(
+            "redraw",
+            &vec![vec![rmpv::Value::from("flush"), rmpv::Value::Array(vec![])]],
+        )
+        .await
+        .unwrap();
+    writer
+        .write_rpc_notification("foo", &args![])
+        .await
+        .unwrap();
+    writer.write_rpc_notification("bar", &(1,)).await.unwrap();
+    writer.write_rpc_request(2, "req", &args![]).await.unwrap();
+
+    assert!(matches!(
+        incoming.next().await,
+        Some(Incoming::Request(req)) if req.msgid == 1
+    ));
+    assert!(matches!(incoming.next().await, Some(Incoming::Redraw(_))));
+    // Subscribed separately.
+    assert!(foo.next().await.is_some());
+    match incoming.next().await {
+        Some(Incoming::Notification(notification)) => {
+            assert_eq!(notification.method, "bar");
+            assert_eq!(
+                notification.params,
+                rmpv::Value::from(vec![rmpv::Value::from(1)])
+            );
+        }
+        msg => panic!("Unexpected message: {:?}", msg),
+    }
+    assert!(matches!(
+        incoming.next().await,
+        Some(Incoming::Request(req)) if req.msgid == 2
+    ));
+}
@@ -0,0 +1,205 @@
+use futures::StreamExt;
+use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+
+use nvim_rs::screen::Screen;
+use nvim_rs::testing::{ui_event, Expect, MockError, MockServer};
+use nvim_rs::types::UiEvent;
+use nvim_rs::{args, CallError, Client, NvimError, NvimErrorKind, Session};
+
+/// Connects a client (and a session driving it) to the mock server.
+fn connect() -> (
+    Client,
+    Session<impl futures::AsyncRead + Unpin>,
+    MockServer<impl futures::AsyncRead + futures::AsyncWrite>,
+) {
+    let (client, server) = tokio::io::duplex(1024 * 64);
+
+    let (reader, writer) = tokio::io::split(client);
+    let (client, writer_task) = Client::new(writer.compat_write());
+    tokio::task::spawn(writer_task);
+
+    let session = Session::new(reader.compat(), client.clone());
+
+    (client, session, MockServer::new(server.compat()))
+}
+
+#[tokio::test]
+async fn scripted_calls_are_responded() {
+    let (client, session, mut server) = connect();
+    let session = tokio::task::spawn(session.run());
+
+    server
+        .expect(
+            Expect::request("nvim_input")
+                .params(args!["ihello"])
+                .returns(6),
+        )
+        .expect(Expect::request("nvim_get_current_line").returns("hello"))
+        .expect(Expect::request("nvim_command").fails("E492: Not an editor command"))
+        .expect(Expect::notification("nvim_ui_try_resize"));
+    let server = tokio::task::spawn(server.run());
+
+    let res = client.nvim_input("ihello").await.unwrap();
+    assert_eq!(res.await, Ok(6));
+
+    let res = client.nvim_get_current_line().await.unwrap();
+    assert_eq!(res.await, Ok("hello".to_string()));
+
+    let res = client.nvim_command("foo").await.unwrap();
+    assert_eq!(
+        res.await,
+        Err(CallError::Error(NvimError {
+            kind: NvimErrorKind::Exception,
+            message: "E492: Not an editor command".to_string(),
+        }))
+    );
+
+    client
+        .notify("nvim_ui_try_resize", args![80, 30])
+        .await
+        .unwrap();
+
+    drop(client);
+    server.await.unwrap().unwrap();
+    assert!(session.await.unwrap().is_err());
+}
+
+#[tokio::test]
+async fn redraw_is_sent_after_call() {
+    let (client, mut session, mut server) = connect();
+    let mut redraw = session.redraw();
+    tokio::task::spawn(session.run());
+
+    server
+        .expect(Expect::request("nvim_ui_attach"))
+        .redraw(vec![
+            ui_event("grid_clear", [(1,)]),
+            ui_event("grid_cursor_goto", [(1, 2, 3)]),
+            ui_event("flush", [()]),
+        ]);
+
+    let res = client
+        .call::<(), _, _>("nvim_ui_attach", args![80, 30])
+        .await
+        .unwrap();
+    server.run_script().await.unwrap();
+    assert_eq!(res.await, Ok(()));
+
+    let batch = redraw.next().await.unwrap();
+    let events = batch.events().unwrap();
+    match events.as_slice() {
+        [UiEvent::GridClear(clear), UiEvent::GridCursorGoto(goto), UiEvent::Flush] => {
+            assert_eq!(clear[0].grid, 1);
+            assert_eq!((goto[0].grid, goto[0].row, goto[0].col), (1, 2, 3));
+        }
+        events => panic!("unexpected events: {:?}", events),
+    }
+}
+
+#[tokio::test]
+async fn redraws_update_the_screen() {
+    let (client, mut session, mut server) = connect();
+    let mut redraw = session.redraw();
+    tokio::task::spawn(session.run());
+
+    let cells = |text: &str| {
+        rmpv::Value::from(
+            text.chars()
+                .map(|c| rmpv::Value::from(vec![rmpv::Value::from(c.to_string())]))
+                .collect::<Vec<_>>(),
+        )
+    };
+    server
+        .expect(Expect::request("nvim_ui_attach"))
+        .redraw(vec![
+            ui_event("grid_resize", [(1, 5, 2)]),
+            ui_event(
+                "grid_line",
+                [(1, 0, 0, cells("hello")), (1, 1, 0, cells("~"))],
+            ),
+            ui_event("grid_cursor_goto", [(1, 0, 4)]),
+            ui_event("flush", [()]),
+        ])
+        .redraw(vec![
+            ui_event("grid_scroll", [(1, 0, 2, 0, 5, 1, 0)]),
+            ui_event("grid_line", [(1, 1, 0, cells("world"))]),
+            ui_event("flush", [()]),
+        ]);
+    let server = tokio::task::spawn(server.run());
+
+    let res = client
+        .call::<(), _, _>("nvim_ui_attach", args![5, 2])
+        .await
+        .unwrap();
+    assert_eq!(res.await, Ok(()));
+
+    let mut screen = Screen::default();
+    let batch = redraw.next().await.unwrap();
+    screen.apply_all(batch.events().unwrap());
+    assert_eq!(screen.grid(1).unwrap().text(), "hello\n~");
+    assert_eq!((screen.cursor().row, screen.cursor().col), (0, 4));
+
+    let batch = redraw.next().await.unwrap();
+    screen.apply_all(batch.events().unwrap());
+    assert_eq!(screen.grid(1).unwrap().text(), "~\nworld");
+
+    drop(client);
+    server.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn unexpected_calls_are_errors() {
+    let (client, session, mut server) = connect();
+    tokio::task::spawn(session.run());
+
+    server.expect(Expect::request("nvim_input").params(args!["a"]));
+    let server = tokio::task::spawn(server.run());
+
+    // Wrong params.
+    let _res = client.nvim_input("b").await.unwrap();
+
+    match server.await.unwrap() {
+        Err(MockError::Unexpected {
+            expected: Some(_),
+            got,
+        }) => assert!(format!("{:?}", got).contains("nvim_input")),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[tokio::test]
+async fn calls_after_the_script_are_errors() {
+    let (client, session, server) = connect();
+    tokio::task::spawn(session.run());
+    let server = tokio::task::spawn(server.run());
+
+    let _res = client.nvim_input("a").await.unwrap();
+
+    assert!(matches!(
+        server.await.unwrap(),
+        Err(MockError::Unexpected { expected: None, .. })
+    ));
+}
+
+#[tokio::test]
+async fn closing_before_the_script_is_done_is_an_error() {
+    let (client, session, mut server) = connect();
+    let session = tokio::task::spawn(session.run());
+
+    server
+        .expect(Expect::request("nvim_input").returns(1))
+        .expect(Expect::request("nvim_input"));
+    let server = tokio::task::spawn(server.run());
+
+    let res = client.nvim_input("a").await.unwrap();
+    assert_eq!(res.await, Ok(1));
+
+    // The session holds on to a client too, so stop it for the connection
+    // to close.
+    session.abort();
+    drop(client);
+    assert!(matches!(
+        server.await.unwrap(),
+        Err(MockError::Closed { remaining: 1 })
+    ));
+}
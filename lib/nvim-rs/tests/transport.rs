@@ -0,0 +1,69 @@
+use nvim_rs::transport;
+
+#[test]
+fn tcp_addresses() {
+    assert!(transport::is_tcp_address("127.0.0.1:6666"));
+    assert!(transport::is_tcp_address("localhost:6666"));
+    assert!(!transport::is_tcp_address("/tmp/nvim.sock"));
+    assert!(!transport::is_tcp_address("/tmp/nvim:6666"));
+    assert!(!transport::is_tcp_address(":6666"));
+    assert!(!transport::is_tcp_address("localhost:port"));
+}
+
+#[cfg(all(unix, feature = "tokio"))]
+#[tokio::test]
+async fn tokio_connect() {
+    use nvim_rs::testing::{Expect, MockServer};
+    use tokio_util::compat::TokioAsyncReadCompatExt;
+
+    let dir = std::env::temp_dir().join(format!("nvim-rs-transport-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let socket = dir.join("tokio.sock");
+    let _ = std::fs::remove_file(&socket);
+    let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+
+    let server = tokio::task::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = MockServer::new(stream.compat());
+        server.expect(Expect::request("nvim_get_current_line").returns("hello"));
+        server.run_script().await
+    });
+
+    let (client, session) = transport::tokio::connect(socket.to_str().unwrap())
+        .await
+        .unwrap();
+    tokio::task::spawn(session.run());
+
+    let res = client.nvim_get_current_line().await.unwrap();
+    assert_eq!(res.await, Ok("hello".to_string()));
+    server.await.unwrap().unwrap();
+
+    let _ = std::fs::remove_file(&socket);
+}
+
+#[cfg(feature = "async-std")]
+#[test]
+fn async_std_connect() {
+    use nvim_rs::testing::{Expect, MockServer};
+
+    async_std::task::block_on(async {
+        let listener = async_std::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let server = async_std::task::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut server = MockServer::new(stream);
+            server.expect(Expect::request("nvim_get_current_line").returns("hello"));
+            server.run_script().await
+        });
+
+        let (client, session) = transport::async_std::connect(&address).await.unwrap();
+        async_std::task::spawn(session.run());
+
+        let res = client.nvim_get_current_line().await.unwrap();
+        assert_eq!(res.await, Ok("hello".to_string()));
+        server.await.unwrap();
+    });
+}
@@ -0,0 +1,469 @@
+use std::borrow::Cow;
+
+use nvim_rs::types::{
+    decode_buf_event, decode_redraw, decode_redraw_params, keysets, ApiInfo, BufEvent, Buffer,
+    OptionSet, Redraw, Tabpage, UiEvent, UiOptions, Window,
+};
+
+#[test]
+fn handles_decode_from_ext() {
+    // Ext type 1 (window) with positive fixint 3 as its payload, the same way
+    // neovim encodes it.
+    let bytes = [0xd4, 0x01, 0x03];
+
+    let win: Window = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(win, Window::new(3));
+
+    let value: rmpv::Value = rmp_serde::from_slice(&bytes).unwrap();
+    let win: Window = rmpv::ext::from_value(value).unwrap();
+    assert_eq!(win.handle(), 3);
+}
+
+#[test]
+fn handles_roundtrip() {
+    let buf = Buffer::new(1000);
+    let bytes = rmp_serde::to_vec(&buf).unwrap();
+    assert_eq!(rmp_serde::from_slice::<Buffer>(&bytes).unwrap(), buf);
+
+    let value = rmpv::ext::to_value(Tabpage::new(2)).unwrap();
+    assert_eq!(value, rmpv::Value::Ext(Tabpage::EXT_ID, vec![0x02]));
+    assert_eq!(
+        rmpv::ext::from_value::<Tabpage>(value).unwrap(),
+        Tabpage::new(2)
+    );
+}
+
+#[test]
+fn handles_reject_wrong_ext_type() {
+    // Buffer ext, decoded as a tabpage.
+    let bytes = [0xd4, 0x00, 0x01];
+
+    assert!(rmp_serde::from_slice::<Tabpage>(&bytes).is_err());
+}
+
+fn encode(value: &rmpv::Value) -> Vec<u8> {
+    let mut buf = vec![];
+    rmpv::encode::write_value(&mut buf, value).unwrap();
+    buf
+}
+
+fn redraw_params() -> rmpv::Value {
+    rmpv::Value::Array(vec![
+        rmpv::Value::Array(vec![
+            "grid_line".into(),
+            rmpv::Value::Array(vec![
+                1.into(),
+                2.into(),
+                3.into(),
+                rmpv::Value::Array(vec![
+                    rmpv::Value::Array(vec!["a".into(), 7.into()]),
+                    rmpv::Value::Array(vec!["b".into()]),
+                    rmpv::Value::Array(vec![" ".into(), 0.into(), 4.into()]),
+                ]),
+            ]),
+        ]),
+        rmpv::Value::Array(vec![
+            "tabline_update".into(),
+            rmpv::Value::Array(vec![
+                rmpv::Value::Ext(Tabpage::EXT_ID, vec![0x01]),
+                rmpv::Value::Array(vec![rmpv::Value::Map(vec![
+                    ("name".into(), "foo".into()),
+                    ("tab".into(), rmpv::Value::Ext(Tabpage::EXT_ID, vec![0x01])),
+                ])]),
+                rmpv::Value::Ext(Buffer::EXT_ID, vec![0x02]),
+                rmpv::Value::Array(vec![]),
+            ]),
+        ]),
+        rmpv::Value::Array(vec!["flush".into(), rmpv::Value::Array(vec![])]),
+    ])
+}
+
+fn assert_redraw_events(events: &[UiEvent]) {
+    match events {
+        [UiEvent::GridLine(lines), UiEvent::TablineUpdate(tabs), UiEvent::Flush] => {
+            assert_eq!(lines.len(), 1);
+            let line = &lines[0];
+            assert_eq!((line.grid, line.row, line.col_start), (1, 2, 3));
+
+            let cells = line
+                .data
+                .iter()
+                .map(|cell| (cell.text.as_ref(), cell.hl_id, cell.repeat))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                cells,
+                vec![
+                    ("a", Some(7), None),
+                    ("b", None, None),
+                    (" ", Some(0), Some(4))
+                ]
+            );
+
+            assert_eq!(tabs.len(), 1);
+            assert_eq!(tabs[0].current, Tabpage::new(1));
+            assert_eq!(tabs[0].tabs[0].name, "foo");
+            assert_eq!(tabs[0].current_buffer, Buffer::new(2));
+        }
+        events => panic!("unexpected events: {:?}", events),
+    }
+}
+
+#[test]
+fn redraw_decodes_from_bytes() {
+    let msg = encode(&rmpv::Value::Array(vec![
+        2.into(),
+        "redraw".into(),
+        redraw_params(),
+    ]));
+
+    let redraw = Redraw::from_message(&msg).unwrap();
+    let events = redraw.events().unwrap();
+    assert_redraw_events(&events);
+
+    // The cell text is borrowed from the message.
+    match &events[0] {
+        UiEvent::GridLine(lines) => {
+            assert!(matches!(lines[0].data[0].text, Cow::Borrowed("a")))
+        }
+        event => panic!("unexpected event: {}", event),
+    }
+
+    let events = events
+        .into_iter()
+        .map(UiEvent::into_owned)
+        .collect::<Vec<_>>();
+    assert_redraw_events(&events);
+}
+
+#[test]
+fn redraw_decodes_from_value() {
+    let events = decode_redraw_params(redraw_params()).unwrap();
+    assert_redraw_events(&events);
+}
+
+#[test]
+fn redraw_from_other_messages_is_none() {
+    let notification = encode(&rmpv::Value::Array(vec![
+        2.into(),
+        "foo".into(),
+        rmpv::Value::Array(vec![]),
+    ]));
+    assert!(Redraw::from_message(&notification).is_none());
+
+    let request = encode(&rmpv::Value::Array(vec![
+        0.into(),
+        1.into(),
+        "redraw".into(),
+        rmpv::Value::Array(vec![]),
+    ]));
+    assert!(Redraw::from_message(&request).is_none());
+}
+
+fn encode_events(events: Vec<rmpv::Value>) -> Vec<u8> {
+    encode(&rmpv::Value::Array(events))
+}
+
+#[test]
+fn unknown_ui_events_are_decoded() {
+    let params = encode_events(vec![
+        rmpv::Value::Array(vec![
+            "some_future_event".into(),
+            rmpv::Value::Array(vec![1.into(), "foo".into()]),
+            rmpv::Value::Array(vec![2.into(), "bar".into()]),
+        ]),
+        rmpv::Value::Array(vec!["flush".into(), rmpv::Value::Array(vec![])]),
+    ]);
+
+    match decode_redraw(&params).unwrap().as_slice() {
+        [UiEvent::Unknown { name, params }, UiEvent::Flush] => {
+            assert_eq!(name, "some_future_event");
+            assert_eq!(
+                params,
+                &vec![
+                    rmpv::Value::Array(vec![1.into(), "foo".into()]),
+                    rmpv::Value::Array(vec![2.into(), "bar".into()]),
+                ]
+            );
+        }
+        events => panic!("unexpected events: {:?}", events),
+    }
+}
+
+#[test]
+fn extra_ui_event_params_are_ignored() {
+    let params = encode_events(vec![
+        rmpv::Value::Array(vec![
+            "grid_resize".into(),
+            rmpv::Value::Array(vec![1.into(), 80.into(), 24.into(), "new param".into()]),
+        ]),
+        rmpv::Value::Array(vec![
+            "option_set".into(),
+            rmpv::Value::Array(vec!["linespace".into(), 3.into(), true.into()]),
+        ]),
+        rmpv::Value::Array(vec![
+            "grid_line".into(),
+            rmpv::Value::Array(vec![
+                1.into(),
+                0.into(),
+                0.into(),
+                rmpv::Value::Array(vec![rmpv::Value::Array(vec![
+                    "a".into(),
+                    1.into(),
+                    2.into(),
+                    "new field".into(),
+                ])]),
+            ]),
+        ]),
+    ]);
+
+    match decode_redraw(&params).unwrap().as_slice() {
+        [UiEvent::GridResize(resize), UiEvent::OptionSet(options), UiEvent::GridLine(lines)] => {
+            assert_eq!(
+                (resize[0].grid, resize[0].width, resize[0].height),
+                (1, 80, 24)
+            );
+            assert!(matches!(options[0], OptionSet::Linespace(3)));
+            assert_eq!(lines[0].data[0].text, "a");
+            assert_eq!(lines[0].data[0].repeat, Some(2));
+        }
+        events => panic!("unexpected events: {:?}", events),
+    }
+}
+
+#[test]
+fn malformed_ui_events_are_errors() {
+    for event in [
+        // Missing params.
+        rmpv::Value::Array(vec![
+            "grid_resize".into(),
+            rmpv::Value::Array(vec![1.into(), 80.into()]),
+        ]),
+        // Bad param type.
+        rmpv::Value::Array(vec![
+            "grid_resize".into(),
+            rmpv::Value::Array(vec![1.into(), "80".into(), 24.into()]),
+        ]),
+        // Empty grid_line cell.
+        rmpv::Value::Array(vec![
+            "grid_line".into(),
+            rmpv::Value::Array(vec![
+                1.into(),
+                0.into(),
+                0.into(),
+                rmpv::Value::Array(vec![rmpv::Value::Array(vec![])]),
+            ]),
+        ]),
+        // Missing option value.
+        rmpv::Value::Array(vec![
+            "option_set".into(),
+            rmpv::Value::Array(vec!["guifont".into()]),
+        ]),
+        // No event name.
+        rmpv::Value::Array(vec![]),
+    ] {
+        assert!(decode_redraw(&encode_events(vec![event.clone()])).is_err());
+        assert!(decode_redraw_params(rmpv::Value::Array(vec![event])).is_err());
+    }
+}
+
+#[test]
+fn msg_events_decode_highlighted_content() {
+    let chunk = |hl_id: i64, text: &str| rmpv::Value::Array(vec![hl_id.into(), text.into()]);
+    let params = encode_events(vec![
+        rmpv::Value::Array(vec![
+            "msg_show".into(),
+            rmpv::Value::Array(vec![
+                "emsg".into(),
+                rmpv::Value::Array(vec![chunk(3, "E492: "), chunk(0, "Not an editor command")]),
+                false.into(),
+            ]),
+        ]),
+        rmpv::Value::Array(vec![
+            "msg_history_show".into(),
+            rmpv::Value::Array(vec![rmpv::Value::Array(vec![
+                rmpv::Value::Array(vec!["".into(), rmpv::Value::Array(vec![chunk(0, "hello")])]),
+                rmpv::Value::Array(vec![
+                    "echoerr".into(),
+                    rmpv::Value::Array(vec![chunk(5, "oops")]),
+                ]),
+            ])]),
+        ]),
+    ]);
+
+    match decode_redraw(&params).unwrap().as_slice() {
+        [UiEvent::MsgShow(show), UiEvent::MsgHistoryShow(history)] => {
+            assert_eq!(show[0].kind, "emsg");
+            assert!(!show[0].replace_last);
+            assert_eq!(
+                show[0]
+                    .content
+                    .iter()
+                    .map(|c| (c.hl_id, c.text.as_str()))
+                    .collect::<Vec<_>>(),
+                vec![(3, "E492: "), (0, "Not an editor command")]
+            );
+
+            let entries = &history[0].entries;
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].kind, "");
+            assert_eq!(entries[0].content[0].text, "hello");
+            assert_eq!(entries[1].kind, "echoerr");
+            assert_eq!(entries[1].content[0].hl_id, 5);
+        }
+        events => panic!("unexpected events: {:?}", events),
+    }
+}
+
+#[test]
+fn win_viewport_scroll_delta_is_optional() {
+    let win = rmpv::ext::to_value(Window::new(1000)).unwrap();
+    let viewport = |scroll_delta: Option<i64>| {
+        let mut params: Vec<rmpv::Value> = vec![
+            2.into(),
+            win.clone(),
+            10.into(),
+            30.into(),
+            12.into(),
+            0.into(),
+            100.into(),
+        ];
+        params.extend(scroll_delta.map(rmpv::Value::from));
+        rmpv::Value::Array(vec!["win_viewport".into(), rmpv::Value::Array(params)])
+    };
+
+    let params = encode_events(vec![viewport(Some(-3)), viewport(None)]);
+    match decode_redraw(&params).unwrap().as_slice() {
+        [UiEvent::WinViewport(new), UiEvent::WinViewport(old)] => {
+            assert_eq!(new[0].win, Window::new(1000));
+            assert_eq!((new[0].topline, new[0].botline), (10, 30));
+            assert_eq!(new[0].line_count, 100);
+            assert_eq!(new[0].scroll_delta, Some(-3));
+            assert_eq!(old[0].scroll_delta, None);
+        }
+        events => panic!("unexpected events: {:?}", events),
+    }
+}
+
+#[test]
+fn keysets_encode_only_the_set_keys() {
+    let config = keysets::FloatConfig::default()
+        .relative("win")
+        .win(Window::new(1000))
+        .width(10)
+        .border("single");
+
+    let expected = rmpv::Value::Map(vec![
+        ("width".into(), 10.into()),
+        ("relative".into(), "win".into()),
+        (
+            "win".into(),
+            rmpv::ext::to_value(Window::new(1000)).unwrap(),
+        ),
+        ("border".into(), "single".into()),
+    ]);
+    assert_eq!(rmp_serde::to_vec_named(&config).unwrap(), encode(&expected));
+
+    // Nested keysets are encoded as maps too.
+    let cmd = keysets::Cmd::default()
+        .cmd("edit")
+        .mods(keysets::CmdMods::default().vertical(true));
+    let expected = rmpv::Value::Map(vec![
+        ("cmd".into(), "edit".into()),
+        (
+            "mods".into(),
+            rmpv::Value::Map(vec![("vertical".into(), true.into())]),
+        ),
+    ]);
+    assert_eq!(rmp_serde::to_vec_named(&cmd).unwrap(), encode(&expected));
+
+    assert_eq!(
+        rmp_serde::to_vec_named(&keysets::Option::default()).unwrap(),
+        encode(&rmpv::Value::Map(vec![]))
+    );
+}
+
+#[test]
+fn api_info_decodes_the_runtime_parts() {
+    let version = rmpv::Value::Map(vec![
+        ("major".into(), 0.into()),
+        ("minor".into(), 7.into()),
+        ("patch".into(), 2.into()),
+        ("api_level".into(), 9.into()),
+        ("api_compatible".into(), 0.into()),
+        ("api_prerelease".into(), false.into()),
+    ]);
+    let metadata = rmpv::Value::Map(vec![
+        ("version".into(), version),
+        ("functions".into(), rmpv::Value::Array(vec![])),
+        (
+            "ui_options".into(),
+            rmpv::Value::Array(vec![
+                "rgb".into(),
+                "ext_linegrid".into(),
+                "ext_multigrid".into(),
+            ]),
+        ),
+    ]);
+
+    let info: ApiInfo =
+        rmpv::ext::from_value(rmpv::Value::Array(vec![3.into(), metadata])).unwrap();
+    assert_eq!(info.channel, 3);
+    assert_eq!(info.metadata.version.to_string(), "v0.7.2");
+    assert!(info.metadata.version.supports(9));
+    assert!(!info.metadata.version.supports(10));
+
+    let mut opts = UiOptions {
+        rgb: true,
+        ext_linegrid: true,
+        ext_multigrid: true,
+        ext_messages: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        opts.retain_supported(&info.metadata.ui_options),
+        vec!["ext_messages"]
+    );
+    assert!(opts.rgb && opts.ext_linegrid && opts.ext_multigrid && !opts.ext_messages);
+}
+
+#[test]
+fn buf_events_are_decoded() {
+    let buf = rmpv::ext::to_value(Buffer::new(2)).unwrap();
+
+    let params = rmpv::Value::Array(vec![
+        buf.clone(),
+        rmpv::Value::Nil,
+        0.into(),
+        (-1).into(),
+        rmpv::Value::Array(vec!["foo".into(), "".into()]),
+        false.into(),
+    ]);
+    match decode_buf_event("nvim_buf_lines_event", params) {
+        Some(Ok(BufEvent::Lines(lines))) => {
+            assert_eq!(lines.buf, Buffer::new(2));
+            assert_eq!(lines.changedtick, None);
+            assert_eq!((lines.firstline, lines.lastline), (0, -1));
+            assert_eq!(lines.linedata, vec!["foo", ""]);
+            assert!(!lines.more);
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    // Extra params (e.g. from newer versions of neovim) are ignored.
+    let params = rmpv::Value::Array(vec![buf.clone(), 5.into(), "extra".into()]);
+    match decode_buf_event("nvim_buf_changedtick_event", params) {
+        Some(Ok(BufEvent::ChangedTick(tick))) => assert_eq!(tick.changedtick, 5),
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let event = decode_buf_event("nvim_buf_detach_event", rmpv::Value::Array(vec![buf]));
+    assert!(matches!(event, Some(Ok(BufEvent::Detach(_)))));
+    assert_eq!(event.unwrap().unwrap().buffer(), Buffer::new(2));
+
+    assert!(
+        decode_buf_event("nvim_buf_lines_event", rmpv::Value::Array(vec![]))
+            .unwrap()
+            .is_err()
+    );
+    assert!(decode_buf_event("redraw", rmpv::Value::Array(vec![])).is_none());
+}
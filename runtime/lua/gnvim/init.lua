@@ -30,6 +30,35 @@ function M.notify(fn, ...)
   end
 end
 
+--- Send request to the first available gnvim GUI, and return its response.
+---
+---@param fn Function to call
+---@param ... Arguments for fn
+function M.request(fn, ...)
+  local chan = M.find_gnvim_chans()[1]
+  if chan == nil then
+    error('no gnvim ui attached')
+  end
+
+  return vim.rpcrequest(chan, "gnvim", {
+    ['fn'] = fn,
+    ['args'] = ...,
+  })
+end
+
+--- Sets up the autocommands that tell gnvim about the changes it can't see
+--- from the ui events. Called by gnvim when it attaches.
+function M.setup_autocmds()
+  local group = vim.api.nvim_create_augroup('gnvim', { clear = true })
+
+  vim.api.nvim_create_autocmd({ 'BufEnter', 'BufWinEnter' }, {
+    group = group,
+    callback = function()
+      M.notify('buf_enter', vim.api.nvim_get_current_win())
+    end,
+  })
+end
+
 function M.echo_repeat(msg, times)
   M.notify('echo_repeat', {
     msg = msg,
@@ -41,6 +70,10 @@ function M.gtk_debugger()
   M.notify('gtk_debugger')
 end
 
+function M.detach()
+  M.notify('detach')
+end
+
 function M.set_cursor_blink_transition(t)
   M.notify('cursor_blink_transition', t)
 end
@@ -53,4 +86,25 @@ function M.set_scroll_transition(t)
   M.notify('scroll_transition', t)
 end
 
+--- Font metrics in pixels: `char_width`, `height` and `baseline`.
+function M.get_font_metrics()
+  return M.request('get_font_metrics')
+end
+
+--- Size of the gnvim window in pixels: `width` and `height`.
+function M.get_window_size_px()
+  return M.request('get_window_size_px')
+end
+
+--- Current transitions: `cursor_blink`, `cursor_position` and `scroll`.
+function M.get_transitions()
+  return M.request('get_transitions')
+end
+
+--- Windows displayed outside of the main window, as a list of `grid` and
+--- `win` pairs.
+function M.list_external_windows()
+  return M.request('list_external_windows')
+end
+
 return M
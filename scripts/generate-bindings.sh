@@ -1,7 +1,12 @@
 #!/bin/bash
 set -xe
 
-nvim --api-info | cargo run --bin apigen functions | sponge lib/nvim-rs/src/gen.rs
+# The api level gnvim targets. Nvim needs to support it, so bump it only when
+# newer api is needed.
+API_LEVEL=10
+
+nvim --api-info | cargo run --bin apigen functions $API_LEVEL | sponge lib/nvim-rs/src/gen.rs
 nvim --api-info | cargo run --bin apigen uievents | sponge lib/nvim-rs/src/types/gen.rs
+nvim --api-info | cargo run --bin apigen keysets | sponge lib/nvim-rs/src/types/keysets.rs
 
 cargo fmt
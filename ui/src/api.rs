@@ -11,9 +11,13 @@ pub enum GnvimEvent {
     // NOTE(ville): Demo event.
     EchoRepeat(EchoRepeat),
     GtkDebugger,
+    Detach,
     CursorBlinkTransition(f64),
     CursorPositionTransition(f64),
     ScrollTransition(f64),
+    /// A window (the handle) entered a buffer. See `setup_autocmds` in the
+    /// runtime files.
+    BufEnter(i64),
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -22,3 +26,53 @@ pub struct EchoRepeat {
     pub msg: String,
     pub times: usize,
 }
+
+/// Requests from `require('gnvim').request()`. Same format as `GnvimEvent`,
+/// but the value returned to the caller is the response.
+#[derive(Debug, serde::Deserialize)]
+#[serde(
+    crate = "nvim::serde",
+    rename_all = "snake_case",
+    tag = "fn",
+    content = "args"
+)]
+pub enum GnvimRequest {
+    GetFontMetrics,
+    GetWindowSizePx,
+    GetTransitions,
+    ListExternalWindows,
+}
+
+/// Font metrics, in pixels.
+#[derive(Debug, serde::Serialize)]
+#[serde(crate = "nvim::serde")]
+pub struct FontMetrics {
+    pub char_width: f32,
+    pub height: f32,
+    pub baseline: f32,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(crate = "nvim::serde")]
+pub struct WindowSize {
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Transition durations, in milliseconds.
+#[derive(Debug, serde::Serialize)]
+#[serde(crate = "nvim::serde")]
+pub struct Transitions {
+    pub cursor_blink: f64,
+    pub cursor_position: f64,
+    pub scroll: f64,
+}
+
+/// A window displayed outside of the main window (see `:help ui-multigrid`).
+#[derive(Debug, serde::Serialize)]
+#[serde(crate = "nvim::serde")]
+pub struct ExternalWindow {
+    pub grid: i64,
+    /// Handle of the window, i.e. the window id on the lua side.
+    pub win: i64,
+}
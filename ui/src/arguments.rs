@@ -1,8 +1,11 @@
 use std::ffi::OsString;
 use std::ops::Deref;
+use std::path::PathBuf;
 
 use gtk::glib;
 
+use crate::nvim::Transport;
+
 #[derive(clap::Parser, Default, Debug, Clone)]
 #[clap(author, version)]
 pub struct Arguments {
@@ -19,6 +22,36 @@ pub struct Arguments {
     )]
     pub rtp: String,
 
+    /// Connect to a running neovim server instead of spawning one. Either a
+    /// path to a unix socket, or a tcp address in the form of `host:port`.
+    #[clap(
+        long,
+        visible_alias = "reattach",
+        name = "ADDRESS",
+        parse(try_from_str = parse_server),
+        conflicts_with_all = &["FILES", "ARGS", "LISTEN"]
+    )]
+    pub server: Option<Transport>,
+
+    /// Start neovim as a server listening on the given address. The server
+    /// keeps running if the ui is detached from it (see `:help gnvim-detach`).
+    #[clap(long, name = "LISTEN")]
+    pub listen: Option<String>,
+
+    /// Record the rpc traffic to a file, for reproducing rendering bugs with
+    /// `--replay`.
+    #[clap(long, name = "RECORD", parse(from_os_str), conflicts_with = "REPLAY")]
+    pub record: Option<PathBuf>,
+
+    /// Replay a recording made with `--record`, without nvim.
+    #[clap(
+        long,
+        name = "REPLAY",
+        parse(from_os_str),
+        conflicts_with_all = &["FILES", "ARGS", "ADDRESS", "LISTEN"]
+    )]
+    pub replay: Option<PathBuf>,
+
     /// Files to open.
     #[clap(name = "FILES")]
     pub files: Vec<OsString>,
@@ -33,12 +66,21 @@ pub struct Arguments {
 
 impl Arguments {
     pub fn nvim_cmd_args(&self) -> Vec<OsString> {
-        let mut args: Vec<OsString> = vec![
-            self.nvim.clone(),
-            OsString::from("--embed"),
+        let mut args: Vec<OsString> = vec![self.nvim.clone()];
+
+        match self.listen {
+            Some(ref address) => args.extend([
+                OsString::from("--headless"),
+                OsString::from("--listen"),
+                OsString::from(address),
+            ]),
+            None => args.push(OsString::from("--embed")),
+        }
+
+        args.extend([
             OsString::from("--cmd"),
             OsString::from(format!("let &rtp.=',{}'", self.rtp)),
-        ];
+        ]);
 
         args.extend_from_slice(&self.nvim_args);
         args.extend_from_slice(&self.files);
@@ -46,11 +88,33 @@ impl Arguments {
         args
     }
 
+    /// How to connect to neovim.
+    pub fn transport(&self) -> Transport {
+        if let Some(ref path) = self.replay {
+            return Transport::Replay(path.clone());
+        }
+
+        match (&self.server, &self.listen) {
+            (Some(server), _) => server.clone(),
+            (None, Some(address)) => Transport::Listen {
+                args: self.nvim_cmd_args(),
+                address: address.clone(),
+                inherit_fds: self.stdin_fd.is_some(),
+            },
+            (None, None) => Transport::Embed {
+                args: self.nvim_cmd_args(),
+                inherit_fds: self.stdin_fd.is_some(),
+            },
+        }
+    }
+
     /// Wrapper around `clap::Praser::parse`. Handle's `Self::stdin_fd`.
     pub fn parse() -> Self {
         let mut args: Self = clap::Parser::parse();
 
-        if atty::isnt(atty::Stream::Stdin) {
+        // NOTE: The stdin fd can't be passed to a remote server, and
+        // there is no nvim to pass it to during replay.
+        if args.server.is_none() && args.replay.is_none() && atty::isnt(atty::Stream::Stdin) {
             args.stdin_fd = dup_stdin();
         }
 
@@ -58,6 +122,11 @@ impl Arguments {
     }
 }
 
+/// Parses `--server`, so that an invalid address is a usage error.
+fn parse_server(address: &str) -> Result<Transport, String> {
+    Transport::server(address).map_err(|err| err.to_string())
+}
+
 fn dup_stdin() -> Option<i32> {
     cfg_if::cfg_if! {
         if #[cfg(unix)] {
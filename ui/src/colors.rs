@@ -4,6 +4,9 @@ use gtk::gdk;
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub enum HlGroup {
+    ErrorMsg,
+    ModeMsg,
+    MsgArea,
     MsgSeparator,
     Pmenu,
     PmenuSel,
@@ -1,33 +1,53 @@
 use std::cell::{Cell, RefCell};
-use std::ffi::OsStr;
 use std::rc::Rc;
 
-use nvim::dict;
+use nvim::{args, dict};
 use nvim::serde::Deserialize;
 use nvim::types::uievents::{DefaultColorsSet, HlGroupSet, PopupmenuSelect, PopupmenuShow};
 use nvim::types::UiEvent;
-use nvim::types::{OptionSet, UiOptions};
+use nvim::types::{ApiInfo, ApiVersion, OptionSet, UiOptions};
 
 use glib::subclass::InitializingObject;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 use gtk::CompositeTemplate;
 use gtk::{
-    gdk,
+    gdk, gio,
     glib::{self, clone},
 };
 
-use gio_compat::CompatRead;
-use nvim::rpc::RpcReader;
+use futures::StreamExt;
+use nvim::handler::{error_value, HandlerResult};
+use nvim::rpc::{record::Recorder, ReadError};
+use nvim::{Incoming, RequestHandlers, Session, MIN_API_LEVEL};
 
-use crate::api::GnvimEvent;
+use crate::api::{ExternalWindow, FontMetrics, GnvimEvent, GnvimRequest, Transitions, WindowSize};
 use crate::boxed::{ModeInfo, ShowTabline};
 use crate::colors::{Color, Colors, HlGroup};
-use crate::components::{Omnibar, Overflower, Shell, Tabline};
+use crate::components::{Messages, Omnibar, Overflower, Shell, Tabline};
 use crate::font::Font;
-use crate::nvim::Neovim;
-use crate::warn;
+use crate::nvim::{Neovim, Reader, Transport};
 use crate::{arguments::BoxedArguments, spawn_local, SCALE};
+use crate::{log, warn};
+
+/// Appends the path (first argument) to the runtime path, unless it's
+/// already there (e.g. from a previous attachment).
+const APPEND_RTP_LUA: &str = r#"
+    local path = ...
+    if not vim.tbl_contains(vim.opt.rtp:get(), path) then
+        vim.opt.rtp:append(path)
+    end
+"#;
+
+/// Sets up gnvim's autocommands, if gnvim's runtime files are available.
+/// Returns if they were.
+const SETUP_AUTOCMDS_LUA: &str = r#"
+    local ok, gnvim = pcall(require, 'gnvim')
+    if ok then
+        gnvim.setup_autocmds()
+    end
+    return ok
+"#;
 
 #[derive(CompositeTemplate, Default)]
 #[template(resource = "/com/github/vhakulinen/gnvim/application.ui")]
@@ -40,11 +60,15 @@ pub struct AppWindow {
     tabline: TemplateChild<Tabline>,
     #[template_child(id = "omnibar")]
     omnibar: TemplateChild<Omnibar>,
+    #[template_child(id = "messages")]
+    messages: TemplateChild<Messages>,
 
     css_provider: gtk::CssProvider,
 
     args: RefCell<BoxedArguments>,
     nvim: Neovim,
+    /// Handlers for the requests neovim sends to us.
+    request_handlers: RefCell<RequestHandlers>,
 
     colors: Rc<RefCell<Colors>>,
     font: RefCell<Font>,
@@ -57,37 +81,32 @@ pub struct AppWindow {
     /// Set when attributes affecting our CSS changed, and we need to regenerate
     /// the css.
     css_on_flush: Cell<bool>,
+    /// Set when we've detached from nvim, so that nvim is left running when
+    /// the window closes.
+    detached: Cell<bool>,
+    /// Recorder for the rpc traffic, if `--record` is set.
+    recorder: RefCell<Option<Recorder>>,
 }
 
 impl AppWindow {
-    async fn io_loop(&self, obj: super::AppWindow, reader: CompatRead) {
-        use nvim::rpc::{message::Notification, Message};
-        let mut reader: RpcReader<CompatRead> = reader.into();
-
-        loop {
-            let msg = reader.recv().await.unwrap();
-            match msg {
-                Message::Response(res) => {
-                    self.nvim
-                        .client()
-                        .await
-                        .handle_response(res)
-                        .expect("failed to handle nvim response");
-                }
-                Message::Request(req) => {
-                    println!("Got request from nvim: {:?}", req);
-                }
-                Message::Notification(Notification { method, params, .. }) => {
-                    match method.as_ref() {
-                        "redraw" => {
-                            let events = nvim::decode_redraw_params(params)
-                                .expect("failed to decode redraw notification");
-
-                            events
-                                .into_iter()
-                                .for_each(|event| self.handle_ui_event(&obj, event))
-                        }
-                        "gnvim" => match params {
+    async fn io_loop(&self, obj: super::AppWindow, reader: Reader) {
+        let mut session = Session::new(reader, self.nvim.client());
+        if let Some(recorder) = self.recorder.borrow().clone() {
+            session.record(recorder);
+        }
+        let mut incoming = session.incoming();
+
+        let handle_incoming = async {
+            while let Some(msg) = incoming.next().await {
+                match msg {
+                    Incoming::Redraw(redraw) => match redraw.events() {
+                        Ok(events) => events
+                            .into_iter()
+                            .for_each(|event| self.handle_ui_event(&obj, event)),
+                        Err(err) => warn!("failed to decode redraw notification: {}", err),
+                    },
+                    Incoming::Notification(notification) if notification.method == "gnvim" => {
+                        match notification.params {
                             rmpv::Value::Array(params) => params
                                 .into_iter()
                                 .map(GnvimEvent::deserialize)
@@ -96,18 +115,202 @@ impl AppWindow {
                                     Err(err) => warn!("failed to parse gnvim event: {:?}", err),
                                 }),
                             params => warn!("unexpected gnvim params: {:?}", params),
-                        },
-                        _ => {
-                            println!("Unexpected notification: {}", method);
+                        }
+                    }
+                    Incoming::Notification(notification) => {
+                        warn!("unexpected notification: {}", notification.method)
+                    }
+                    Incoming::Request(req) => {
+                        let msgid = req.msgid;
+                        // NOTE: Don't hold the borrow while the request is
+                        // being handled.
+                        let res = self.request_handlers.borrow().handle(req);
+                        let res = res.await;
+
+                        if let Err(err) = self.nvim.client().respond(msgid, res).await {
+                            warn!("failed to respond to nvim request: {:?}", err);
                         }
                     }
                 }
             }
+        };
+
+        let (res, _) = futures::join!(session.run(), handle_incoming);
+        match res {
+            // Neovim exited.
+            Err(ReadError::IOError(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {}
+            Err(err) => warn!("nvim connection failed: {:?}", err),
+            Ok(()) => {}
         }
+
+        if let Some(err) = self
+            .recorder
+            .borrow()
+            .as_ref()
+            .and_then(|recorder| recorder.take_error())
+        {
+            warn!("recording failed: {}", err);
+        }
+
+        obj.close();
+    }
+
+    /// Attaches the ui to nvim.
+    ///
+    /// If nvim doesn't support `MIN_API_LEVEL`, an error is shown instead. The ui
+    /// extensions nvim doesn't support are turned off from `uiopts`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uiopts` - Options for `nvim_ui_attach`.
+    /// * `rtp` - Runtime path to add for gnvim's runtime files, if nvim
+    /// wasn't started with it.
+    fn attach(&self, obj: &super::AppWindow, mut uiopts: UiOptions, rtp: Option<String>) {
+        spawn_local!(clone!(@weak self.nvim as nvim, @weak obj => async move {
+            if let Some(rtp) = rtp {
+                let res = nvim
+                    .client()
+                    .nvim_exec_lua(APPEND_RTP_LUA, vec![rtp.into()])
+                    .await
+                    .expect("call to nvim failed");
+
+                if let Err(err) = res.await {
+                    warn!("failed to set runtime path: {}", err);
+                }
+            }
+
+            let res = nvim
+                .client()
+                .call::<ApiInfo, _, _>("nvim_get_api_info", args![])
+                .await
+                .expect("call to nvim failed");
+            match res.await {
+                Ok(info) => {
+                    let version = &info.metadata.version;
+                    if !version.supports(MIN_API_LEVEL) {
+                        obj.imp().show_nvim_incompatible(&obj, version);
+                        return;
+                    }
+
+                    for ext in uiopts.retain_supported(&info.metadata.ui_options) {
+                        warn!("nvim {} doesn't support {}, disabling it", version, ext);
+                    }
+                }
+                // NOTE: Try to attach anyways, nvim will tell if
+                // something is wrong.
+                Err(err) => warn!("nvim_get_api_info failed: {}", err),
+            }
+
+            // Set the client info and attach the ui in a single round trip.
+            // NOTE: The calls are pipelined instead of using an atomic
+            // batch, so that a failing nvim_set_client_info won't prevent us
+            // from attaching.
+            let res = nvim
+                .client()
+                .batch()
+                .call::<(), _, _>("nvim_set_client_info", args![
+                    "gnvim",
+                    // TODO(ville): Tell the version in client info.
+                    dict![],
+                    "ui",
+                    dict![],
+                    dict![]
+                ])
+                .call::<(), _, _>("nvim_ui_attach", args![80, 30, uiopts])
+                .pipelined()
+                .await
+                .expect("call to nvim failed");
+
+            if let Err(err) = res.await {
+                warn!("attaching to nvim failed: {}", err);
+            }
+
+            let res = nvim
+                .client()
+                .call::<bool, _, _>("nvim_exec_lua", args![SETUP_AUTOCMDS_LUA, Vec::<rmpv::Value>::new()])
+                .await
+                .expect("call to nvim failed");
+
+            match res.await {
+                Ok(true) => {}
+                Ok(false) => warn!("gnvim runtime files not found, check --rtp"),
+                Err(err) => warn!("failed to set up autocmds: {}", err),
+            }
+        }));
+    }
+
+    /// Tells the user that nvim doesn't support the api level we need, and
+    /// closes the window once the message is dismissed.
+    fn show_nvim_incompatible(&self, obj: &super::AppWindow, version: &ApiVersion) {
+        let (title, reason) = if version.api_level < MIN_API_LEVEL {
+            ("Neovim is too old", "is too old")
+        } else {
+            ("Neovim is too new", "is no longer compatible")
+        };
+        warn!(
+            "nvim {} (api levels {}-{}) {} for api level {}",
+            version, version.api_compatible, version.api_level, reason, MIN_API_LEVEL
+        );
+
+        let dialog = gtk::MessageDialog::builder()
+            .transient_for(obj)
+            .modal(true)
+            .message_type(gtk::MessageType::Error)
+            .buttons(gtk::ButtonsType::Close)
+            .text(title)
+            .secondary_text(&format!(
+                "Gnvim requires Neovim that supports API level {}, but Neovim {} supports API levels {}-{}.",
+                MIN_API_LEVEL, version, version.api_compatible, version.api_level
+            ))
+            .build();
+
+        dialog.connect_response(clone!(@weak obj => move |dialog, _| {
+            dialog.close();
+            obj.close();
+        }));
+
+        dialog.show();
+    }
+
+    /// Detaches the ui from nvim and closes the window, leaving nvim running.
+    fn detach(&self, obj: &super::AppWindow) {
+        let address = match self.nvim.address() {
+            Some(address) => address,
+            None => {
+                warn!("can't detach from embedded nvim, use --listen instead");
+                return;
+            }
+        };
+
+        spawn_local!(clone!(@weak obj => async move {
+            let res = match obj.imp().nvim.client().nvim_ui_detach().await {
+                Ok(res) => res,
+                Err(err) => {
+                    warn!("failed to send nvim_ui_detach: {}", err);
+                    return;
+                }
+            };
+            if let Err(err) = res.await {
+                warn!("nvim_ui_detach failed: {}", err);
+                return;
+            }
+
+            log!(
+                glib::LogLevel::Message,
+                "detached from nvim, reattach with: gnvim --reattach {}",
+                address
+            );
+
+            obj.imp().detached.set(true);
+            obj.close();
+        }));
     }
 
     fn handle_hl_group_set(&self, event: HlGroupSet) {
         if let Some(group) = match event.name.as_ref() {
+            "ErrorMsg" => Some(HlGroup::ErrorMsg),
+            "ModeMsg" => Some(HlGroup::ModeMsg),
+            "MsgArea" => Some(HlGroup::MsgArea),
             "MsgSeparator" => Some(HlGroup::MsgSeparator),
             "Pmenu" => Some(HlGroup::Pmenu),
             "PmenuSel" => Some(HlGroup::PmenuSel),
@@ -168,14 +371,18 @@ impl AppWindow {
                 spawn_local!(clone!(@weak self.nvim as nvim => async move {
                     let res = nvim
                         .client()
-                        .await
                         .nvim_echo(msg.into(), false, &dict![])
                         .await
                         .unwrap();
 
-                    res.await.expect("nvim_echo failed");
+                    if let Err(err) = res.await {
+                        warn!("nvim_echo failed: {}", err);
+                    }
                 }));
             }
+            GnvimEvent::Detach => {
+                self.detach(obj);
+            }
             GnvimEvent::GtkDebugger => {
                 self.enable_debugging(obj, true);
             }
@@ -188,7 +395,56 @@ impl AppWindow {
             GnvimEvent::ScrollTransition(t) => {
                 self.shell.set_scroll_transition(t);
             }
+            GnvimEvent::BufEnter(win) => {
+                self.shell.handle_buf_enter(win);
+            }
+        }
+    }
+
+    fn handle_gnvim_request(&self, obj: &super::AppWindow, params: rmpv::Value) -> HandlerResult {
+        // NOTE: The params are the args of `rpcrequest`, of which the
+        // first one is the request.
+        let req = match params {
+            rmpv::Value::Array(params) => params.into_iter().next(),
+            _ => None,
         }
+        .ok_or_else(|| error_value("missing gnvim request"))?;
+        let req = GnvimRequest::deserialize(req)
+            .map_err(|err| error_value(format!("invalid gnvim request: {}", err)))?;
+
+        let res = match req {
+            GnvimRequest::GetFontMetrics => {
+                let font = self.font.borrow();
+                rmpv::ext::to_value(FontMetrics {
+                    char_width: font.char_width() / SCALE,
+                    height: font.height() / SCALE,
+                    baseline: font.baseline() / SCALE,
+                })
+            }
+            GnvimRequest::GetWindowSizePx => rmpv::ext::to_value(WindowSize {
+                width: obj.width(),
+                height: obj.height(),
+            }),
+            GnvimRequest::GetTransitions => rmpv::ext::to_value(Transitions {
+                cursor_blink: self.shell.cursor_blink_transition(),
+                cursor_position: self.shell.cursor_position_transition(),
+                scroll: self.shell.scroll_transition(),
+            }),
+            GnvimRequest::ListExternalWindows => rmpv::ext::to_value(
+                self.shell
+                    .external_grids()
+                    .iter()
+                    .filter_map(|grid| {
+                        Some(ExternalWindow {
+                            grid: grid.id(),
+                            win: grid.nvim_window()?.handle(),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+        };
+
+        res.map_err(|err| error_value(format!("failed to encode gnvim response: {}", err)))
     }
 
     fn handle_ui_event(&self, obj: &super::AppWindow, event: UiEvent) {
@@ -207,10 +463,10 @@ impl AppWindow {
             }),
             UiEvent::ModeChange(events) => events.into_iter().for_each(|event| {
                 let modes = self.mode_infos.borrow();
-                let mode = modes
-                    .get(event.mode_idx as usize)
-                    .expect("invalid mode_idx");
-                self.shell.handle_mode_change(mode);
+                match modes.get(event.mode_idx as usize) {
+                    Some(mode) => self.shell.handle_mode_change(mode),
+                    None => warn!("invalid mode_idx: {}", event.mode_idx),
+                }
             }),
             UiEvent::MouseOn => {}
             UiEvent::MouseOff => {}
@@ -240,6 +496,9 @@ impl AppWindow {
                     let pmenu_thumb = colors.get_hl_group(&HlGroup::PmenuThumb);
                     let pmenu_bar = colors.get_hl_group(&HlGroup::PmenuSbar);
                     let msgsep = colors.get_hl_group(&HlGroup::MsgSeparator);
+                    let errormsg = colors.get_hl_group(&HlGroup::ErrorMsg);
+                    let modemsg = colors.get_hl_group(&HlGroup::ModeMsg);
+                    let msgarea = colors.get_hl_group(&HlGroup::MsgArea);
                     let tablinefill = colors.get_hl_group(&HlGroup::TabLineFill);
                     let tabline = colors.get_hl_group(&HlGroup::TabLine);
                     let tablinesel = colors.get_hl_group(&HlGroup::TabLineSel);
@@ -288,6 +547,23 @@ impl AppWindow {
                                     border-color: #{pmenuthumb_bg};
                                 }}
 
+                                grid-scrollbar {{
+                                    min-width: 8px;
+                                    background-color: alpha(#{pmenusbar_bg}, 0.5);
+                                    opacity: 0;
+                                    transition: opacity 300ms ease-out;
+                                }}
+
+                                grid-scrollbar.active {{
+                                    opacity: 1;
+                                }}
+
+                                grid-scrollbar .thumb {{
+                                    min-height: 20px;
+                                    background-color: #{pmenuthumb_bg};
+                                    border-radius: 4px;
+                                }}
+
                                 tabline {{
                                     background-color: #{tablinefill_bg};
                                     box-shadow: inset -2px -70px 10px -70px rgba(0,0,0,0.75);
@@ -327,6 +603,23 @@ impl AppWindow {
                                         {omnibar_pad}px;
                                 }}
 
+                                omnibar label.chip {{
+                                    padding: 0 {omnibar_pad}px;
+                                    margin: 0 {omnibar_pad}px;
+                                    border-radius: 3px;
+                                }}
+
+                                omnibar .chip.mode {{
+                                    background-color: #{modemsg_bg};
+                                    color: #{modemsg_fg};
+                                }}
+
+                                omnibar .chip.showcmd,
+                                omnibar .chip.ruler {{
+                                    background-color: #{msgarea_bg};
+                                    color: #{msgarea_fg};
+                                }}
+
                                 omnibar cmdline {{
                                     padding: {omnibar_pad}px;
                                 }}
@@ -336,10 +629,37 @@ impl AppWindow {
                                     color: #{fg};
                                     caret-color: #{fg};
                                 }}
+
+                                messages {{
+                                    margin: 10px;
+                                }}
+
+                                messages .message,
+                                messages .message-history {{
+                                    background-color: #{bg};
+                                    color: #{fg};
+                                    border: 1px solid shade(#{fg}, 0.5);
+                                    border-radius: 3px;
+                                    padding: 5px 8px;
+                                }}
+
+                                messages .message-history .message {{
+                                    border: 0;
+                                    padding: 0;
+                                }}
+
+                                messages .message.error {{
+                                    border-color: #{errormsg_fg};
+                                }}
                             "#,
                             bg = colors.bg.as_hex(),
                             fg = colors.fg.as_hex(),
                             msgsep = msgsep.fg().as_hex(),
+                            errormsg_fg = errormsg.fg().as_hex(),
+                            modemsg_fg = modemsg.fg().as_hex(),
+                            modemsg_bg = modemsg.bg().as_hex(),
+                            msgarea_fg = msgarea.fg().as_hex(),
+                            msgarea_bg = msgarea.bg().as_hex(),
                             pmenu_fg = pmenu.fg().as_hex(),
                             pmenu_bg = pmenu.bg().as_hex(),
                             pmenu_sel_fg = pmenu_sel.fg().as_hex(),
@@ -412,8 +732,9 @@ impl AppWindow {
             UiEvent::MsgSetPos(events) => events
                 .into_iter()
                 .for_each(|event| self.shell.handle_msg_set_pos(event, &self.font.borrow())),
-            // TODO(ville): Scrollbars?
-            UiEvent::WinViewport(_) => {}
+            UiEvent::WinViewport(events) => events
+                .into_iter()
+                .for_each(|event| self.shell.handle_win_viewport(event)),
 
             // popupmenu events
             UiEvent::PopupmenuShow(events) => events
@@ -453,7 +774,29 @@ impl AppWindow {
                     .handle_cmdline_block_append(event, &self.colors.borrow())
             }),
 
-            event => panic!("Unhandled ui event: {}", event),
+            // message events
+            UiEvent::MsgShow(events) => events
+                .into_iter()
+                .for_each(|event| self.messages.handle_msg_show(event, &self.colors.borrow())),
+            UiEvent::MsgClear => self.messages.handle_msg_clear(),
+            UiEvent::MsgHistoryShow(events) => events.into_iter().for_each(|event| {
+                self.messages
+                    .handle_msg_history_show(event, &self.colors.borrow())
+            }),
+            UiEvent::MsgHistoryClear => self.messages.handle_msg_history_clear(),
+            UiEvent::MsgShowmode(events) => events
+                .into_iter()
+                .for_each(|event| self.omnibar.handle_msg_showmode(event)),
+            UiEvent::MsgShowcmd(events) => events
+                .into_iter()
+                .for_each(|event| self.omnibar.handle_msg_showcmd(event)),
+            UiEvent::MsgRuler(events) => events
+                .into_iter()
+                .for_each(|event| self.omnibar.handle_msg_ruler(event)),
+
+            UiEvent::Unknown { name, .. } => warn!("unknown ui event: {}", name),
+
+            event => warn!("unhandled ui event: {}", event),
         }
     }
 
@@ -489,13 +832,14 @@ impl AppWindow {
         spawn_local!(clone!(@weak self.nvim as nvim => async move {
             let res = nvim
                 .client()
-                .await
                 .nvim_input(&input)
                 .await
                 .expect("call to nvim failed");
 
             // TODO(ville): nvim_input handle the returned bytes written value.
-            res.await.expect("nvim_input failed");
+            if let Err(err) = res.await {
+                warn!("nvim_input failed: {}", err);
+            }
         }));
     }
 
@@ -554,6 +898,7 @@ impl ObjectSubclass for AppWindow {
 
     fn class_init(klass: &mut Self::Class) {
         Overflower::ensure_type();
+        Messages::ensure_type();
         Omnibar::ensure_type();
         Shell::ensure_type();
         Tabline::ensure_type();
@@ -583,42 +928,54 @@ impl ObjectImpl for AppWindow {
             ext_popupmenu: true,
             ext_tabline: true,
             ext_cmdline: true,
+            ext_messages: true,
             stdin_fd: self.args.borrow().stdin_fd,
             ..Default::default()
         };
-        let args = self.args.borrow().nvim_cmd_args();
-        let args: Vec<&OsStr> = args.iter().map(|a| a.as_ref()).collect();
-        let reader = self.nvim.open(&args, uiopts.stdin_fd.is_some());
+        let (reader, rtp) = {
+            let args = self.args.borrow();
+            let transport = args.transport();
+            let reader = self
+                .nvim
+                .open(&transport)
+                .unwrap_or_else(|err| panic!("failed to open nvim: {}", err));
+
+            if let Some(ref path) = args.record {
+                let file = std::fs::File::create(path)
+                    .unwrap_or_else(|err| panic!("failed to create recording: {}", err));
+                let recorder = Recorder::new(std::io::BufWriter::new(file));
+
+                self.nvim.client().record(recorder.clone());
+                self.recorder.replace(Some(recorder));
+            }
+
+            // NOTE: Embedded nvim gets the runtime path from the
+            // command line, but a remote server has to be told about it.
+            let rtp = matches!(transport, Transport::Socket { .. }).then(|| args.rtp.clone());
+
+            (reader, rtp)
+        };
+
+        self.request_handlers.borrow_mut().register(
+            "gnvim",
+            clone!(@weak obj => @default-return Err(error_value("gnvim is closing")),
+            move |params: rmpv::Value| {
+                obj.imp().handle_gnvim_request(&obj, params)
+            }),
+        );
 
         // Start io loop.
         spawn_local!(clone!(@strong obj as app => async move {
             app.imp().io_loop(app.clone(), reader).await;
         }));
 
-        // Call nvim_ui_attach.
-        spawn_local!(clone!(@weak self.nvim as nvim => async move {
-            let res = nvim
-                .client()
-                .await
-                .nvim_set_client_info(
-                    "gnvim",
-                    // TODO(ville): Tell the version in client info.
-                    &dict![],
-                    "ui",
-                    &dict![],
-                    &dict![],
-                ).await.expect("call to nvim failed");
-
-            res.await.expect("nvim_set_client_info failed");
+        self.attach(obj, uiopts, rtp);
 
-            let res = nvim
-                .client()
-                .await
-                .nvim_ui_attach(80, 30, uiopts)
-                .await.expect("call to nvim failed");
-
-            res.await.expect("nvim_ui_attach failed");
+        let detach = gio::SimpleAction::new("detach", None);
+        detach.connect_activate(clone!(@weak obj => move |_, _| {
+            obj.imp().detach(&obj);
         }));
+        obj.add_action(&detach);
 
         // TODO(ville): Figure out if we should use preedit or not.
         self.im_context.set_use_preedit(false);
@@ -713,7 +1070,17 @@ impl WidgetImpl for AppWindow {
     }
 }
 
-impl WindowImpl for AppWindow {}
+impl WindowImpl for AppWindow {
+    fn close_request(&self, window: &Self::Type) -> gtk::Inhibit {
+        // NOTE: Like embedded nvim, nvim that we spawned as a server
+        // dies with the window, unless we detached from it.
+        if !self.detached.get() {
+            self.nvim.kill();
+        }
+
+        self.parent_close_request(window)
+    }
+}
 
 impl ApplicationWindowImpl for AppWindow {}
 
@@ -10,7 +10,7 @@ use gtk::{
     subclass::prelude::*,
 };
 
-use crate::{components::Grid, spawn_local};
+use crate::{components::Grid, spawn_local, warn};
 
 #[derive(Default)]
 pub struct ExternalWindow {
@@ -36,14 +36,15 @@ impl ExternalWindow {
                     let res = obj
                         .nvim()
                         .client()
-                        .await
                         .nvim_ui_try_resize_grid(
                             obj.grid_id(),
                             cols.max(1) as i64, rows.max(1) as i64)
                         .await
                         .unwrap();
 
-                    res.await.expect("nvim_ui_try_resize failed");
+                    if let Err(err) = res.await {
+                        warn!("nvim_ui_try_resize failed: {}", err);
+                    }
                 }));
 
                 // Clear after our selves, so we don't try to remove
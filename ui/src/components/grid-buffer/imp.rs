@@ -1,4 +1,5 @@
 use std::cell::{self, RefCell};
+use std::collections::BTreeMap;
 
 use gtk::subclass::prelude::*;
 use gtk::{glib, graphene, gsk, prelude::*};
@@ -16,16 +17,20 @@ pub struct GridBuffer {
     /// Background nodes.
     pub background_nodes: RefCell<Vec<gsk::RenderNode>>,
 
-    /// Node containing the "background" buffer (used for the scroll effect).
-    pub scroll_node: RefCell<Option<gsk::RenderNode>>,
     /// Callback id for scroll animation.
     pub scroll_tick: RefCell<Option<gtk::TickCallbackId>>,
     /// Scroll transition time.
     pub scroll_transition: cell::Cell<f64>,
     /// Y offset for the main buffer.
     pub y_offset: cell::Cell<f32>,
-    /// Y offset for the scroll/background buffer.
-    pub y_offset_scroll: cell::Cell<f32>,
+    /// Scroll delta (in rows) from the viewport, animated on the next flush.
+    pub pending_scroll: cell::Cell<i64>,
+    /// Accumulated scroll position (in rows) of our first row.
+    pub scroll_pos: cell::Cell<i64>,
+    /// Render nodes of recently displayed rows, keyed by their scroll
+    /// position. Used to fill in the rows that are outside of our content
+    /// during the scroll animation.
+    pub history: RefCell<BTreeMap<i64, gsk::RenderNode>>,
 
     pub font: RefCell<Font>,
 }
@@ -79,6 +84,7 @@ impl ObjectImpl for GridBuffer {
                     .borrow_mut()
                     .iter_mut()
                     .for_each(|row| row.cells.iter_mut().for_each(Cell::clear_nodes));
+                self.history.borrow_mut().clear();
             }
             "scroll-transition" => self
                 .scroll_transition
@@ -99,14 +105,30 @@ impl WidgetImpl for GridBuffer {
             req.height() as f32,
         ));
 
-        if let Some(ref node) = *self.scroll_node.borrow() {
-            snapshot.save();
-            snapshot.translate(&graphene::Point::new(0.0, self.y_offset_scroll.get()));
-            snapshot.append_node(node);
-            snapshot.restore();
+        let y_offset = self.y_offset.get();
+        if y_offset != 0.0 {
+            // Fill the rows our content doesn't cover from the history.
+            let row_h = self.font.borrow().height() / SCALE;
+            let pos = self.scroll_pos.get();
+            let len = self.rows.borrow().len() as i64;
+            for (row, node) in self.history.borrow().iter() {
+                if (pos..pos + len).contains(row) {
+                    continue;
+                }
+
+                let y = (row - pos) as f32 * row_h + y_offset;
+                if y + row_h <= 0.0 || y >= req.height() as f32 {
+                    continue;
+                }
+
+                snapshot.save();
+                snapshot.translate(&graphene::Point::new(0.0, y));
+                snapshot.append_node(node);
+                snapshot.restore();
+            }
         }
 
-        snapshot.translate(&graphene::Point::new(0.0, self.y_offset.get()));
+        snapshot.translate(&graphene::Point::new(0.0, y_offset));
 
         for node in self.background_nodes.borrow().iter() {
             snapshot.append_node(node);
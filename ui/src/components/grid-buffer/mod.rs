@@ -3,7 +3,7 @@ use std::cell::{Ref, RefMut};
 use gtk::{glib, graphene, gsk, prelude::*, subclass::prelude::*};
 use nvim::types::uievents::GridScroll;
 
-use crate::{colors::Colors, font::Font, math::ease_out_cubic, warn};
+use crate::{colors::Colors, font::Font, math::ease_out_cubic, warn, SCALE};
 
 mod imp;
 pub mod row;
@@ -45,6 +45,8 @@ impl GridBuffer {
     }
 
     pub fn resize(&self, width: usize, height: usize) {
+        self.clear_history();
+
         let mut rows = self.imp().rows.borrow_mut();
         rows.resize_with(height, Default::default);
 
@@ -74,10 +76,23 @@ impl GridBuffer {
     pub fn flush(&self, colors: &Colors) {
         let imp = self.imp();
 
+        let delta = imp.pending_scroll.take();
+        if delta != 0 {
+            imp.scroll_pos.set(imp.scroll_pos.get() + delta);
+            self.scroll_transition(delta);
+        }
+
         let ctx = self.pango_context();
 
+        let record = imp.scroll_transition.get() > 0.0;
+        let mut changed = Vec::new();
+
         let font = imp.font.borrow();
         for (i, row) in imp.rows.borrow_mut().iter_mut().enumerate() {
+            if record {
+                changed.push(delta != 0 || row.is_dirty());
+            }
+
             row.generate_nodes(&ctx, colors, &font, i as f32);
         }
 
@@ -92,9 +107,70 @@ impl GridBuffer {
             .upcast(),
         );
 
+        if record {
+            self.record_history(colors, alloc.width() as f32, &changed);
+        }
+
         self.queue_draw();
     }
 
+    /// Saves the `changed` rows, and the ones that are missing from it, to
+    /// the history. Drops the rows that are more than a screenful away from
+    /// the viewport.
+    fn record_history(&self, colors: &Colors, width: f32, changed: &[bool]) {
+        let imp = self.imp();
+        let rows = imp.rows.borrow();
+        let row_h = imp.font.borrow().height() / SCALE;
+        let pos = imp.scroll_pos.get();
+        let len = rows.len() as i64;
+
+        let mut history = imp.history.borrow_mut();
+        for ((i, row), changed) in rows.iter().enumerate().zip(changed) {
+            let key = pos + i as i64;
+            if !changed && history.contains_key(&key) {
+                continue;
+            }
+
+            let y = i as f32 * row_h;
+            let snapshot = gtk::Snapshot::new();
+            // Move the row to the origin.
+            snapshot.translate(&graphene::Point::new(0.0, -y));
+            snapshot.append_node(
+                &gsk::ColorNode::new(&colors.bg, &graphene::Rect::new(0.0, y, width, row_h))
+                    .upcast(),
+            );
+            for nodes in row.render_node_iter() {
+                if let Some(ref nodes) = *nodes {
+                    snapshot.append_node(&nodes.bg);
+                }
+            }
+            for nodes in row.render_node_iter() {
+                if let Some(ref nodes) = *nodes {
+                    snapshot.append_node(&nodes.fg);
+                }
+            }
+
+            if let Some(node) = snapshot.to_node() {
+                history.insert(key, node);
+            }
+        }
+
+        history.retain(|row, _| *row >= pos - len && *row < pos + 2 * len);
+    }
+
+    /// Forgets the rows recorded for the scroll animation, e.g. when the
+    /// window shows a different buffer.
+    pub fn clear_history(&self) {
+        self.imp().history.borrow_mut().clear();
+    }
+
+    /// Sets the amount of rows the viewport scrolled, to be animated on the
+    /// next flush.
+    pub fn scroll_viewport(&self, delta: i64) {
+        let imp = self.imp();
+        imp.pending_scroll.set(imp.pending_scroll.get() + delta);
+    }
+
     fn scroll_region(event: &GridScroll) -> (Box<dyn Iterator<Item = i64>>, i64) {
         if event.rows > 0 {
             let top = event.top + event.rows;
@@ -115,10 +191,6 @@ impl GridBuffer {
             "at the moment of writing, grid_scroll event documents cols to be always zero"
         );
 
-        // The scroll animation requires a snapshot of our current buffer,
-        // so start it before making changes to our content.
-        self.scroll_transition(&event);
-
         let left = event.left as usize;
         let right = event.right as usize;
         let (iter, count) = GridBuffer::scroll_region(&event);
@@ -148,20 +220,23 @@ impl GridBuffer {
         }
     }
 
-    /// Creates a scrolling effect based on grid scroll event.
-    fn scroll_transition(&self, event: &GridScroll) {
+    /// Creates a scrolling effect, moving our content from `delta` rows
+    /// away to its place.
+    fn scroll_transition(&self, delta: i64) {
         let imp = self.imp();
-        let start_time = self
-            .frame_clock()
-            .expect("failed to get frame clock")
-            .frame_time() as f64;
+        let start_time = match self.frame_clock() {
+            Some(clock) => clock.frame_time() as f64,
+            // Not visible, nothing to animate.
+            None => return,
+        };
 
-        let snapshot = gtk::Snapshot::new();
-        imp.snapshot(self, &snapshot);
-        imp.scroll_node.replace(snapshot.to_node());
+        // NOTE: Neovim's delta is an approximation when scrolling
+        // more than a screenful, so don't travel further than that.
+        let len = imp.rows.borrow().len() as i64;
+        let delta = delta.clamp(-len, len);
 
         let target_y = 0.0;
-        let start_y = imp.y_offset.get() + imp.font.borrow().row_to_y(event.rows as f64) as f32;
+        let start_y = imp.y_offset.get() + imp.font.borrow().row_to_y(delta as f64) as f32;
         let end_time = start_time + imp.scroll_transition.get();
         let old_id =
             imp.scroll_tick
@@ -180,14 +255,12 @@ impl GridBuffer {
                                 as f32;
                         let y = start_y + ((target_y - start_y) * t);
 
-                        imp.y_offset_scroll.set(y - start_y);
                         imp.y_offset.set(y);
                         this.queue_draw();
 
                         Continue(true)
                     } else {
                         imp.y_offset.set(target_y);
-                        imp.scroll_node.replace(None);
                         this.queue_draw();
 
                         Continue(false)
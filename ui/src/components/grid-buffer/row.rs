@@ -101,13 +101,18 @@ impl Row {
             for _ in 0..data.repeat.unwrap_or(1) {
                 let mut cell = iter.next().expect("too long grid line event");
                 cell.hl_id = hl_id;
-                cell.text = data.text.clone();
+                cell.text = data.text.to_string();
                 cell.double_width = double_width;
                 cell.clear_nodes();
             }
         }
     }
 
+    /// If some of the cells need their render nodes generated.
+    pub fn is_dirty(&self) -> bool {
+        self.cells.iter().any(|cell| cell.nodes.borrow().is_none())
+    }
+
     pub fn render_node_iter(&self) -> RenderNodeIter<'_> {
         RenderNodeIter {
             inner: self.cells.iter().peekable(),
@@ -4,15 +4,25 @@ use gtk::glib::subclass::InitializingObject;
 use gtk::subclass::prelude::*;
 use gtk::{
     glib::{self, clone},
+    graphene, gsk,
     prelude::*,
 };
 use nvim::types::Window;
 
 use crate::boxed::ModeInfo;
-use crate::components::{Cursor, ExternalWindow, GridBuffer};
+use crate::components::{Cursor, ExternalWindow, GridBuffer, Scrollbar};
 use crate::font::Font;
 use crate::nvim::Neovim;
-use crate::spawn_local;
+use crate::{some_or_return, spawn_local, warn};
+
+/// Scrolls the window (first argument) so that the line (second argument)
+/// is at the top.
+const SCROLL_WINDOW_LUA: &str = r#"
+    local win, topline = ...
+    vim.api.nvim_win_call(win, function()
+        vim.fn.winrestview({ topline = topline })
+    end)
+"#;
 
 #[derive(gtk::CompositeTemplate, Default)]
 #[template(resource = "/com/github/vhakulinen/gnvim/grid.ui")]
@@ -23,13 +33,18 @@ pub struct Grid {
     /// The content.
     #[template_child(id = "buffer")]
     pub buffer: TemplateChild<GridBuffer>,
+    /// Scrollbar for the window's viewport.
+    #[template_child(id = "scrollbar")]
+    pub scrollbar: TemplateChild<Scrollbar>,
 
     pub font: RefCell<Font>,
 
     /// The grid id from neovim.
     pub id: Cell<i64>,
     /// Neovim window associated to this grid.
-    pub nvim_window: RefCell<Option<Window>>,
+    pub nvim_window: Cell<Option<Window>>,
+    /// The window's topline from the previous `win_viewport`.
+    pub topline: Cell<Option<i64>>,
     pub nvim: RefCell<Neovim>,
     /// If grid is the active grid or not.
     pub active: Cell<bool>,
@@ -55,6 +70,7 @@ impl ObjectSubclass for Grid {
     fn class_init(klass: &mut Self::Class) {
         GridBuffer::ensure_type();
         Cursor::ensure_type();
+        Scrollbar::ensure_type();
 
         klass.bind_template();
     }
@@ -88,7 +104,6 @@ impl ObjectImpl for Grid {
                     let res = obj
                         .nvim()
                         .client()
-                        .await
                         .nvim_input_mouse(
                             mouse.as_nvim_input(),
                             action.as_nvim_action(),
@@ -99,15 +114,39 @@ impl ObjectImpl for Grid {
                         )
                         .await.expect("call to nvim failed");
 
-                    res.await.expect("nvim_input_mouse failed");
+                    if let Err(err) = res.await {
+                        warn!("nvim_input_mouse failed: {}", err);
+                    }
                 });
             }),
-        )
+        );
+
+        self.scrollbar
+            .connect_scroll(clone!(@weak obj => move |topline| {
+                let win = some_or_return!(obj.nvim_window(), "scrollbar: grid has no window");
+
+                spawn_local!(async move {
+                    let res = obj
+                        .nvim()
+                        .client()
+                        .nvim_exec_lua(
+                            SCROLL_WINDOW_LUA,
+                            vec![win.handle().into(), topline.into()],
+                        )
+                        .await
+                        .expect("call to nvim failed");
+
+                    if let Err(err) = res.await {
+                        warn!("scrolling window failed: {}", err);
+                    }
+                });
+            }));
     }
 
     fn dispose(&self, _obj: &Self::Type) {
         self.buffer.unparent();
         self.cursor.unparent();
+        self.scrollbar.unparent();
     }
 
     fn properties() -> &'static [glib::ParamSpec] {
@@ -237,14 +276,30 @@ impl WidgetImpl for Grid {
     fn size_allocate(&self, widget: &Self::Type, width: i32, height: i32, baseline: i32) {
         self.parent_size_allocate(widget, width, height, baseline);
 
+        let scrollbar = self.scrollbar.upcast_ref::<gtk::Widget>();
         let mut child: Option<gtk::Widget> = widget.first_child();
         while let Some(sib) = child {
-            if sib.should_layout() {
+            if sib.should_layout() && sib != *scrollbar {
                 let (req, _) = sib.preferred_size();
                 sib.allocate(req.width(), req.height(), -1, None);
             }
 
             child = sib.next_sibling();
         }
+
+        // The scrollbar is on top of the buffer's right edge.
+        if scrollbar.should_layout() {
+            let (req, _) = scrollbar.preferred_size();
+            scrollbar.allocate(
+                req.width(),
+                height,
+                -1,
+                Some(
+                    &gsk::Transform::new()
+                        .translate(&graphene::Point::new((width - req.width()) as f32, 0.0))
+                        .expect("failed to translate transform"),
+                ),
+            );
+        }
     }
 }
@@ -6,7 +6,7 @@ use std::{
 use gtk::{glib, glib::clone, prelude::*, subclass::prelude::*};
 
 use nvim::types::{
-    uievents::{GridLine, GridResize, GridScroll},
+    uievents::{GridLine, GridResize, GridScroll, WinViewport},
     Window,
 };
 
@@ -19,7 +19,7 @@ use crate::{
     some_or_return,
 };
 
-use super::ExternalWindow;
+use super::{scrollbar::Viewport, ExternalWindow};
 
 mod imp;
 
@@ -68,8 +68,23 @@ impl Grid {
         *external_win = Some(external);
     }
 
+    pub fn is_external(&self) -> bool {
+        self.imp().external_win.borrow().is_some()
+    }
+
+    pub fn nvim_window(&self) -> Option<Window> {
+        self.imp().nvim_window.get()
+    }
+
     pub fn set_nvim_window(&self, window: Option<Window>) {
-        self.imp().nvim_window.replace(window);
+        let imp = self.imp();
+        if imp.nvim_window.replace(window) != window {
+            self.reset_viewport();
+        }
+
+        if window.is_none() {
+            imp.scrollbar.set_visible(false);
+        }
     }
 
     pub fn connect_mouse<F>(&self, f: F)
@@ -225,6 +240,34 @@ impl Grid {
         self.imp().buffer.scroll(event);
     }
 
+    pub fn viewport(&self, event: WinViewport) {
+        let imp = self.imp();
+
+        let delta = match imp.topline.replace(Some(event.topline)) {
+            // NOTE: Older versions of neovim don't send the scroll delta, so
+            // derive it from the topline. Unlike neovim's delta, this counts
+            // folded lines too.
+            Some(topline) => event.scroll_delta.unwrap_or(event.topline - topline),
+            // Nothing to scroll from.
+            None => 0,
+        };
+        imp.buffer.scroll_viewport(delta);
+
+        imp.scrollbar.set_viewport(Viewport {
+            topline: event.topline,
+            botline: event.botline,
+            line_count: event.line_count,
+        });
+    }
+
+    /// Forgets the previous viewport, so that the next one isn't animated.
+    /// Used when the grid shows a different window or buffer.
+    pub fn reset_viewport(&self) {
+        let imp = self.imp();
+        imp.topline.set(None);
+        imp.buffer.clear_history();
+    }
+
     pub fn mode_change(&self, mode: &ModeInfo) {
         self.set_property("mode-info", mode);
     }
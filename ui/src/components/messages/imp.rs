@@ -0,0 +1,70 @@
+use std::cell::RefCell;
+
+use glib::{clone, subclass::InitializingObject};
+use gtk::{glib, prelude::*, subclass::prelude::*};
+
+/// A message shown in the toast list.
+pub struct Toast {
+    pub label: gtk::Label,
+    /// Errors are not dismissed automatically, nor by `msg_clear`.
+    pub error: bool,
+    /// Dismisses the message automatically. Not set for errors and prompts.
+    pub timeout: Option<glib::SourceId>,
+}
+
+#[derive(gtk::CompositeTemplate, Default)]
+#[template(resource = "/com/github/vhakulinen/gnvim/messages.ui")]
+pub struct Messages {
+    #[template_child(id = "container")]
+    pub container: TemplateChild<gtk::Box>,
+    #[template_child(id = "toast-list")]
+    pub toast_list: TemplateChild<gtk::Box>,
+    #[template_child(id = "history")]
+    pub history: TemplateChild<gtk::Box>,
+    #[template_child(id = "history-list")]
+    pub history_list: TemplateChild<gtk::Box>,
+    #[template_child(id = "history-close")]
+    pub history_close: TemplateChild<gtk::Button>,
+
+    pub toasts: RefCell<Vec<Toast>>,
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for Messages {
+    const NAME: &'static str = "Messages";
+    type Type = super::Messages;
+    type ParentType = gtk::Widget;
+
+    fn class_init(klass: &mut Self::Class) {
+        klass.set_layout_manager_type::<gtk::BinLayout>();
+        klass.set_css_name("messages");
+        klass.bind_template();
+    }
+
+    fn instance_init(obj: &InitializingObject<Self>) {
+        obj.init_template();
+    }
+}
+
+impl ObjectImpl for Messages {
+    fn constructed(&self, obj: &Self::Type) {
+        self.parent_constructed(obj);
+
+        self.history_close
+            .connect_clicked(clone!(@weak obj => move |_| {
+                obj.imp().history.set_visible(false);
+            }));
+    }
+
+    fn dispose(&self, _obj: &Self::Type) {
+        self.toasts
+            .borrow_mut()
+            .drain(..)
+            .filter_map(|toast| toast.timeout)
+            .for_each(|id| id.remove());
+
+        self.container.unparent();
+    }
+}
+
+impl WidgetImpl for Messages {}
@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use gtk::{glib, glib::clone, pango, prelude::*, subclass::prelude::*};
+
+use nvim::types::uievents::{MsgHistoryShow, MsgShow};
+use nvim::types::MsgContent;
+
+use crate::colors::Colors;
+
+mod imp;
+
+/// How long a message is shown before it's dismissed automatically.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(4);
+
+glib::wrapper! {
+    pub struct Messages(ObjectSubclass<imp::Messages>)
+        @extends gtk::Widget,
+        @implements gtk::ConstraintTarget, gtk::Buildable, gtk::Accessible;
+}
+
+impl Messages {
+    pub fn handle_msg_show(&self, event: MsgShow, colors: &Colors) {
+        let imp = self.imp();
+
+        if event.replace_last {
+            let last = imp.toasts.borrow().last().map(|toast| toast.label.clone());
+            if let Some(label) = last {
+                self.dismiss(&label);
+            }
+        }
+
+        // Nothing to show, e.g. from `:echo ""`.
+        if event.content.iter().all(|item| item.text.is_empty()) {
+            return;
+        }
+
+        let label = message_label(&content_markup(&event.content, colors), &event.kind);
+        let error = is_error(&event.kind);
+
+        let gesture_click = gtk::GestureClick::new();
+        gesture_click.connect_released(
+            clone!(@weak self as obj, @weak label => move |_, _, _, _| {
+                obj.dismiss(&label);
+            }),
+        );
+        label.add_controller(&gesture_click);
+
+        let timeout = (!error && !is_prompt(&event.kind)).then(|| {
+            glib::timeout_add_local(
+                MESSAGE_TIMEOUT,
+                clone!(@weak self as obj, @weak label => @default-return Continue(false), move || {
+                    // Clear after our selves, so we don't try to remove
+                    // our id once we're already done.
+                    if let Some(toast) = obj
+                        .imp()
+                        .toasts
+                        .borrow_mut()
+                        .iter_mut()
+                        .find(|toast| toast.label == label)
+                    {
+                        toast.timeout = None;
+                    }
+
+                    obj.dismiss(&label);
+
+                    Continue(false)
+                }),
+            )
+        });
+
+        imp.toast_list.append(&label);
+        imp.toasts.borrow_mut().push(imp::Toast {
+            label,
+            error,
+            timeout,
+        });
+    }
+
+    pub fn handle_msg_clear(&self) {
+        let labels = self
+            .imp()
+            .toasts
+            .borrow()
+            .iter()
+            .filter(|toast| !toast.error)
+            .map(|toast| toast.label.clone())
+            .collect::<Vec<_>>();
+
+        labels.iter().for_each(|label| self.dismiss(label));
+    }
+
+    pub fn handle_msg_history_show(&self, event: MsgHistoryShow, colors: &Colors) {
+        let imp = self.imp();
+
+        self.clear_history();
+        event.entries.iter().for_each(|entry| {
+            imp.history_list.append(&message_label(
+                &content_markup(&entry.content, colors),
+                &entry.kind,
+            ));
+        });
+
+        imp.history.set_visible(true);
+    }
+
+    pub fn handle_msg_history_clear(&self) {
+        self.clear_history();
+    }
+
+    fn clear_history(&self) {
+        let list = &self.imp().history_list;
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+    }
+
+    /// Removes the toast showing `label`.
+    fn dismiss(&self, label: &gtk::Label) {
+        let imp = self.imp();
+
+        let toast = {
+            let mut toasts = imp.toasts.borrow_mut();
+            match toasts.iter().position(|toast| toast.label == *label) {
+                Some(idx) => toasts.remove(idx),
+                None => return,
+            }
+        };
+
+        if let Some(id) = toast.timeout {
+            id.remove();
+        }
+
+        imp.toast_list.remove(&toast.label);
+    }
+}
+
+/// Message kinds that stay visible until they're dismissed.
+fn is_error(kind: &str) -> bool {
+    matches!(kind, "emsg" | "echoerr" | "lua_error" | "rpc_error")
+}
+
+/// Message kinds that wait for the user's input. These stay visible until
+/// neovim clears or replaces them.
+fn is_prompt(kind: &str) -> bool {
+    matches!(kind, "confirm" | "confirm_sub" | "return_prompt")
+}
+
+fn content_markup(content: &[MsgContent], colors: &Colors) -> String {
+    content
+        .iter()
+        .map(|item| colors.get_hl(&item.hl_id).pango_markup(&item.text))
+        .collect()
+}
+
+fn message_label(markup: &str, kind: &str) -> gtk::Label {
+    let label = gtk::Label::builder()
+        .use_markup(true)
+        .label(markup)
+        .wrap(true)
+        .wrap_mode(pango::WrapMode::WordChar)
+        .max_width_chars(80)
+        .xalign(0.0)
+        .build();
+
+    label.add_css_class("message");
+    if is_error(kind) {
+        label.add_css_class("error");
+    }
+
+    label
+}
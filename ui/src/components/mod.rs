@@ -6,10 +6,12 @@ pub mod fixedz;
 pub mod grid;
 #[path = "./grid-buffer/mod.rs"]
 pub mod grid_buffer;
+pub mod messages;
 pub mod msgwin;
 pub mod omnibar;
 pub mod overflower;
 pub mod popupmenu;
+pub mod scrollbar;
 pub mod shell;
 pub mod tabline;
 
@@ -19,9 +21,11 @@ pub use externalwin::ExternalWindow;
 pub use fixedz::Fixedz;
 pub use grid::Grid;
 pub use grid_buffer::GridBuffer;
+pub use messages::Messages;
 pub use msgwin::MsgWin;
 pub use omnibar::Omnibar;
 pub use overflower::Overflower;
 pub use popupmenu::Popupmenu;
+pub use scrollbar::Scrollbar;
 pub use shell::Shell;
 pub use tabline::Tabline;
@@ -5,8 +5,16 @@ use crate::{child_iter::IterChildren, components::Cmdline};
 #[derive(gtk::CompositeTemplate, Default)]
 #[template(resource = "/com/github/vhakulinen/gnvim/omnibar.ui")]
 pub struct Omnibar {
+    #[template_child(id = "title-bar")]
+    pub title_bar: TemplateChild<gtk::Box>,
     #[template_child(id = "title")]
     pub title: TemplateChild<gtk::Label>,
+    #[template_child(id = "mode")]
+    pub mode: TemplateChild<gtk::Label>,
+    #[template_child(id = "showcmd")]
+    pub showcmd: TemplateChild<gtk::Label>,
+    #[template_child(id = "ruler")]
+    pub ruler: TemplateChild<gtk::Label>,
     #[template_child(id = "cmdline-revealer")]
     pub cmdline_revealer: TemplateChild<gtk::Revealer>,
     #[template_child(id = "cmdline")]
@@ -57,7 +65,7 @@ impl ObjectImpl for Omnibar {
             "title" => self.title.label().to_value(),
             "max-height" => self.cmdline.max_height().to_value(),
             "title-height" => {
-                let h = self.title.preferred_size().1.height();
+                let h = self.title_bar.preferred_size().1.height();
 
                 let style_ctx = obj.style_context();
                 let border = style_ctx.border();
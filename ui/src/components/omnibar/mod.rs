@@ -1,8 +1,9 @@
 use gtk::{prelude::*, subclass::prelude::*};
 use nvim::types::uievents::{
     CmdlineBlockAppend, CmdlineBlockShow, CmdlineHide, CmdlinePos, CmdlineShow, CmdlineSpecialChar,
-    PopupmenuSelect, PopupmenuShow,
+    MsgRuler, MsgShowcmd, MsgShowmode, PopupmenuSelect, PopupmenuShow,
 };
+use nvim::types::MsgContent;
 
 use crate::colors::Colors;
 
@@ -76,4 +77,34 @@ impl Omnibar {
     pub fn handle_popupmenu_hide(&self) {
         self.imp().cmdline.popupmenu_hide();
     }
+
+    pub fn handle_msg_showmode(&self, event: MsgShowmode) {
+        // NOTE: Strip the dashes from e.g. "-- INSERT --".
+        let text = content_text(&event.content);
+        set_chip(&self.imp().mode, text.trim().trim_matches('-').trim());
+    }
+
+    pub fn handle_msg_showcmd(&self, event: MsgShowcmd) {
+        set_chip(&self.imp().showcmd, content_text(&event.content).trim());
+    }
+
+    pub fn handle_msg_ruler(&self, event: MsgRuler) {
+        // NOTE: The ruler is padded to its column, collapse the
+        // whitespace so the chip stays compact.
+        let text = content_text(&event.content)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        set_chip(&self.imp().ruler, &text);
+    }
+}
+
+fn content_text(content: &[MsgContent]) -> String {
+    content.iter().map(|item| item.text.as_str()).collect()
+}
+
+/// Sets the chip's text, and hides the chip when there's nothing to show.
+fn set_chip(chip: &gtk::Label, text: &str) {
+    chip.set_text(text);
+    chip.set_visible(!text.is_empty());
 }
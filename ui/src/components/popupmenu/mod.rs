@@ -7,7 +7,7 @@ mod row;
 use nvim::types::PopupmenuItem;
 use row::Row;
 
-use crate::{nvim::Neovim, spawn_local, SCALE};
+use crate::{nvim::Neovim, spawn_local, warn, SCALE};
 
 glib::wrapper! {
     pub struct Popupmenu(ObjectSubclass<imp::Popupmenu>)
@@ -77,12 +77,13 @@ impl Popupmenu {
         spawn_local!(clone!(@weak nvim => async move {
             let res = nvim
                 .client()
-                .await
                 .nvim_ui_pum_set_bounds(w, h, row, col)
                 .await
                 .unwrap();
 
-            res.await.expect("nvim_ui_pum_set_bounds failed");
+            if let Err(err) = res.await {
+                warn!("nvim_ui_pum_set_bounds failed: {}", err);
+            }
         }));
     }
 }
@@ -0,0 +1,105 @@
+use std::cell::{Cell, RefCell};
+
+use gtk::{
+    glib::{self, clone, subclass::InitializingObject},
+    graphene, gsk,
+    prelude::*,
+    subclass::prelude::*,
+};
+
+use super::Viewport;
+
+#[derive(gtk::CompositeTemplate, Default)]
+#[template(resource = "/com/github/vhakulinen/gnvim/scrollbar.ui")]
+pub struct Scrollbar {
+    #[template_child(id = "thumb")]
+    pub thumb: TemplateChild<gtk::Box>,
+
+    pub viewport: Cell<Viewport>,
+    pub gesture_drag: gtk::GestureDrag,
+    pub event_controller_motion: gtk::EventControllerMotion,
+    /// The topline when the current drag started, if we're being dragged.
+    pub drag_start: Cell<Option<f64>>,
+    /// The last (zero based) topline we asked neovim to scroll to during
+    /// the current drag.
+    pub requested_topline: Cell<Option<i64>>,
+    pub hovered: Cell<bool>,
+    /// Timeout for fading out the scrollbar.
+    pub idle_id: RefCell<Option<glib::SourceId>>,
+}
+
+impl Scrollbar {
+    /// Position and height of the thumb, for the given trough height.
+    pub fn thumb_bounds(&self, width: i32, height: i32) -> (i32, i32) {
+        let (top, size) = self.viewport.get().thumb();
+        let (min_h, _, _, _) = self.thumb.measure(gtk::Orientation::Vertical, width);
+
+        let h = ((size * height as f64) as i32).max(min_h).min(height);
+        let y = ((top * height as f64) as i32).min(height - h);
+
+        (y, h)
+    }
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for Scrollbar {
+    const NAME: &'static str = "Scrollbar";
+    type Type = super::Scrollbar;
+    type ParentType = gtk::Widget;
+
+    fn class_init(klass: &mut Self::Class) {
+        klass.set_css_name("grid-scrollbar");
+        klass.bind_template();
+    }
+
+    fn instance_init(obj: &InitializingObject<Self>) {
+        obj.init_template();
+    }
+}
+
+impl ObjectImpl for Scrollbar {
+    fn constructed(&self, obj: &Self::Type) {
+        self.parent_constructed(obj);
+
+        // Only the active scrollbar takes input, see `Scrollbar::wake`.
+        obj.set_can_target(false);
+        obj.add_controller(&self.gesture_drag);
+        obj.add_controller(&self.event_controller_motion);
+
+        self.event_controller_motion
+            .connect_enter(clone!(@weak obj => move |_, _, _| {
+                obj.imp().hovered.set(true);
+                obj.cancel_idle();
+            }));
+        self.event_controller_motion
+            .connect_leave(clone!(@weak obj => move |_| {
+                obj.imp().hovered.set(false);
+                obj.wake();
+            }));
+    }
+
+    fn dispose(&self, _obj: &Self::Type) {
+        if let Some(id) = self.idle_id.take() {
+            id.remove();
+        }
+
+        self.thumb.unparent();
+    }
+}
+
+impl WidgetImpl for Scrollbar {
+    fn size_allocate(&self, _widget: &Self::Type, width: i32, height: i32, _baseline: i32) {
+        let (y, h) = self.thumb_bounds(width, height);
+
+        self.thumb.allocate(
+            width,
+            h,
+            -1,
+            Some(
+                &gsk::Transform::new()
+                    .translate(&graphene::Point::new(0.0, y as f32))
+                    .expect("failed to translate transform"),
+            ),
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use gtk::{glib, glib::clone, prelude::*, subclass::prelude::*};
+
+mod imp;
+
+/// How long the scrollbar stays visible after the viewport changes.
+const IDLE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// The visible lines of a window, from `win_viewport`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// First visible line (zero based).
+    pub topline: i64,
+    /// Line below the last visible line (zero based).
+    pub botline: i64,
+    pub line_count: i64,
+}
+
+impl Viewport {
+    /// Number of visible lines.
+    fn height(&self) -> i64 {
+        (self.botline - self.topline).max(0)
+    }
+
+    /// Position and size of the thumb, as fractions of the trough.
+    fn thumb(&self) -> (f64, f64) {
+        let count = self.line_count.max(1) as f64;
+        let top = (self.topline as f64 / count).clamp(0.0, 1.0);
+        let size = (self.height() as f64 / count).clamp(0.0, 1.0 - top);
+
+        (top, size)
+    }
+
+    fn is_scrollable(&self) -> bool {
+        self.topline > 0 || self.botline < self.line_count
+    }
+
+    fn max_topline(&self) -> i64 {
+        (self.line_count - self.height()).max(0)
+    }
+}
+
+glib::wrapper! {
+    pub struct Scrollbar(ObjectSubclass<imp::Scrollbar>)
+        @extends gtk::Widget,
+        @implements gtk::ConstraintTarget, gtk::Buildable, gtk::Accessible;
+}
+
+impl Scrollbar {
+    pub fn set_viewport(&self, viewport: Viewport) {
+        let imp = self.imp();
+        self.set_visible(viewport.is_scrollable());
+
+        // NOTE: Neovim sends the viewport on every cursor move, only wake up
+        // when the window actually scrolled.
+        if imp.viewport.replace(viewport) != viewport {
+            self.queue_allocate();
+            self.wake();
+        }
+    }
+
+    /// Shows the scrollbar, and fades it out after a while unless it's being
+    /// dragged or hovered.
+    fn wake(&self) {
+        self.add_css_class("active");
+        self.set_can_target(true);
+
+        self.cancel_idle();
+        let imp = self.imp();
+        if imp.drag_start.get().is_some() || imp.hovered.get() {
+            return;
+        }
+
+        let id = glib::timeout_add_local(
+            IDLE_TIMEOUT,
+            clone!(@weak self as obj => @default-return Continue(false), move || {
+                // The idle scrollbar is invisible, so it must not take the
+                // input meant for the window underneath it.
+                obj.remove_css_class("active");
+                obj.set_can_target(false);
+
+                // Clear after our selves, so we don't try to remove
+                // our id once we're already done.
+                obj.imp().idle_id.replace(None);
+
+                Continue(false)
+            }),
+        );
+
+        imp.idle_id.replace(Some(id));
+    }
+
+    fn cancel_idle(&self) {
+        if let Some(id) = self.imp().idle_id.take() {
+            id.remove();
+        }
+    }
+
+    /// Connects `f` to be called with the (one based) topline the window
+    /// should be scrolled to, when the thumb is dragged or the trough is
+    /// clicked.
+    pub fn connect_scroll<F>(&self, f: F)
+    where
+        F: Fn(i64) + 'static + Clone,
+    {
+        let imp = self.imp();
+
+        // Scrolls to `topline`, unless we're already there or it was the
+        // last topline we asked for (the viewport is updated only once
+        // neovim has scrolled).
+        let scroll_to = clone!(@weak self as obj, @strong f => move |topline: f64| {
+            let imp = obj.imp();
+            let viewport = imp.viewport.get();
+            let topline = (topline.round() as i64).clamp(0, viewport.max_topline());
+            let current = imp.requested_topline.get().unwrap_or(viewport.topline);
+
+            if topline != current {
+                imp.requested_topline.set(Some(topline));
+                f(topline + 1);
+            }
+        });
+
+        imp.gesture_drag.connect_drag_begin(
+            clone!(@weak self as obj, @strong scroll_to => move |_, _, y| {
+                let imp = obj.imp();
+                imp.requested_topline.set(None);
+                let viewport = imp.viewport.get();
+                let (thumb_y, thumb_h) = imp.thumb_bounds(obj.width(), obj.height());
+                let y = y as i32;
+
+                let topline = if y >= thumb_y && y < thumb_y + thumb_h {
+                    viewport.topline as f64
+                } else {
+                    // Clicked on the trough, center the thumb to the
+                    // click position.
+                    let line = y as f64 / obj.height().max(1) as f64 * viewport.line_count as f64;
+                    let topline = line - viewport.height() as f64 / 2.0;
+                    scroll_to(topline);
+                    topline
+                };
+
+                imp.drag_start.set(Some(topline));
+                obj.wake();
+            }),
+        );
+
+        imp.gesture_drag.connect_drag_update(
+            clone!(@weak self as obj, @strong scroll_to => move |_, _, dy| {
+                let imp = obj.imp();
+                if let Some(start) = imp.drag_start.get() {
+                    let viewport = imp.viewport.get();
+                    let lines = dy / obj.height().max(1) as f64 * viewport.line_count as f64;
+                    scroll_to(start + lines);
+                }
+            }),
+        );
+
+        imp.gesture_drag
+            .connect_drag_end(clone!(@weak self as obj => move |_, _, _| {
+                let imp = obj.imp();
+                imp.drag_start.set(None);
+                imp.requested_topline.set(None);
+                obj.wake();
+            }));
+    }
+}
@@ -5,6 +5,7 @@ use gtk::{glib, graphene, gsk, prelude::*, subclass::prelude::*};
 use nvim::types::uievents::{
     GridClear, GridCursorGoto, GridDestroy, GridLine, GridResize, GridScroll, MsgSetPos,
     PopupmenuSelect, PopupmenuShow, WinClose, WinExternalPos, WinFloatPos, WinHide, WinPos,
+    WinViewport,
 };
 
 use crate::{boxed::ModeInfo, colors::Colors, font::Font, nvim::Neovim, spawn_local, warn, SCALE};
@@ -62,12 +63,13 @@ impl Shell {
                 spawn_local!(clone!(@weak obj => async move {
                     let res = obj.nvim()
                         .client()
-                        .await
                         .nvim_ui_try_resize_grid(1, cols.max(1) as i64, rows.max(1) as i64)
                         .await
                         .unwrap();
 
-                    res.await.expect("nvim_ui_try_resize failed");
+                    if let Err(err) = res.await {
+                        warn!("nvim_ui_try_resize failed: {}", err);
+                    }
                 }));
 
                 // Clear after our selves, so we don't try to remove
@@ -84,14 +86,26 @@ impl Shell {
         }
     }
 
+    pub fn cursor_blink_transition(&self) -> f64 {
+        self.property("cursor-blink-transition")
+    }
+
     pub fn set_cursor_blink_transition(&self, t: f64) {
         self.set_property("cursor-blink-transition", t);
     }
 
+    pub fn cursor_position_transition(&self) -> f64 {
+        self.property("cursor-position-transition")
+    }
+
     pub fn set_cursor_position_transition(&self, t: f64) {
         self.set_property("cursor-position-transition", t);
     }
 
+    pub fn scroll_transition(&self) -> f64 {
+        self.property("scroll-transition")
+    }
+
     pub fn set_scroll_transition(&self, t: f64) {
         self.set_property("scroll-transition", t);
     }
@@ -112,6 +126,17 @@ impl Shell {
         find_grid_or_return!(self, event.grid).put(event);
     }
 
+    /// The grids displayed in external windows.
+    pub fn external_grids(&self) -> Vec<Grid> {
+        self.imp()
+            .grids
+            .borrow()
+            .iter()
+            .filter(|grid| grid.is_external())
+            .cloned()
+            .collect()
+    }
+
     pub fn font(&self) -> Font {
         self.imp().root_grid.font().clone()
     }
@@ -191,7 +216,10 @@ impl Shell {
     }
 
     pub fn handle_grid_destroy(&self, event: GridDestroy) {
-        assert!(event.grid != 1, "cant do grid_destroy for grid 1");
+        if event.grid == 1 {
+            warn!("cant do grid_destroy for grid 1");
+            return;
+        }
 
         let mut grids = self.imp().grids.borrow_mut();
         if let Some(index) = grids.iter().position(|grid| grid.id() == event.grid) {
@@ -206,7 +234,10 @@ impl Shell {
     }
 
     pub fn handle_win_pos(&self, event: WinPos, font: &Font) {
-        assert!(event.grid != 1, "cant do win_pos for grid 1");
+        if event.grid == 1 {
+            warn!("cant do win_pos for grid 1");
+            return;
+        }
 
         /* NOTE(ville): The reported width and height in this event _might_
          * be different from the actual size of the window when/if at somepoint
@@ -276,22 +307,47 @@ impl Shell {
     }
 
     pub fn handle_win_hide(&self, event: WinHide) {
-        assert!(event.grid != 1, "cant do win_hide for grid 1");
+        if event.grid == 1 {
+            warn!("cant do win_hide for grid 1");
+            return;
+        }
 
         let grid = find_grid_or_return!(self, event.grid);
         grid.unparent();
     }
 
     pub fn handle_win_close(&self, event: WinClose) {
-        assert!(event.grid != 1, "cant do win_close for grid 1");
+        if event.grid == 1 {
+            warn!("cant do win_close for grid 1");
+            return;
+        }
 
         let grid = find_grid_or_return!(self, event.grid);
         grid.set_nvim_window(None);
         grid.unparent();
     }
 
+    pub fn handle_win_viewport(&self, event: WinViewport) {
+        let grid = find_grid_or_return!(self, event.grid);
+        grid.viewport(event);
+    }
+
+    /// Called when the window `win` entered a buffer, which might not be
+    /// the one it showed before.
+    pub fn handle_buf_enter(&self, win: i64) {
+        self.imp()
+            .grids
+            .borrow()
+            .iter()
+            .filter(|grid| grid.nvim_window().map(|window| window.handle()) == Some(win))
+            .for_each(Grid::reset_viewport);
+    }
+
     pub fn handle_win_external_pos(&self, event: WinExternalPos, parent: &gtk::Window) {
-        assert!(event.grid != 1, "cant do win_external_pos for grid 1");
+        if event.grid == 1 {
+            warn!("cant do win_external_pos for grid 1");
+            return;
+        }
 
         let grid = find_grid_or_return!(self, event.grid);
         grid.set_nvim_window(Some(event.win));
@@ -299,7 +355,10 @@ impl Shell {
     }
 
     pub fn handle_msg_set_pos(&self, event: MsgSetPos, font: &Font) {
-        assert!(event.grid != 1, "cant do msg_set_pos for grid 1");
+        if event.grid == 1 {
+            warn!("cant do msg_set_pos for grid 1");
+            return;
+        }
 
         let grid = find_grid_or_return!(self, event.grid);
         let imp = self.imp();
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use gtk::{glib, prelude::*, subclass::prelude::*};
 use nvim::types::{uievents::TablineUpdate, ShowTabline};
 
@@ -16,17 +18,33 @@ impl Tabline {
     pub fn handle_tabline_update(&self, event: TablineUpdate) {
         let imp = self.imp();
 
-        imp.content
+        // Take the existing tabs out, so they can be reused for the tabpages
+        // that still exist.
+        let mut tabs = imp
+            .content
             .iter_children()
-            .for_each(|child| child.unparent());
+            .filter_map(|child| {
+                child.unparent();
+                let child = child.downcast::<tab::Tab>().ok()?;
+                Some((child.tabpage()?, child))
+            })
+            .collect::<HashMap<_, _>>();
 
         let nvim = imp.nvim.borrow();
         for tab in event.tabs.into_iter() {
             let current = tab.tab == event.current;
-            let child = tab::Tab::new(&*nvim, &tab.name, Tabpage(tab.tab));
+            let child = match tabs.remove(&tab.tab) {
+                Some(child) => {
+                    child.set_label(&tab.name);
+                    child
+                }
+                None => tab::Tab::new(&*nvim, &tab.name, Tabpage(tab.tab)),
+            };
 
             if current {
                 child.add_css_class("selected");
+            } else {
+                child.remove_css_class("selected");
             }
 
             imp.content.append(&child);
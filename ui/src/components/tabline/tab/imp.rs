@@ -3,7 +3,7 @@ use std::cell::RefCell;
 use glib::{clone, subclass::InitializingObject};
 use gtk::{glib, prelude::*, subclass::prelude::*};
 
-use crate::{boxed::Tabpage, nvim::Neovim, spawn_local};
+use crate::{boxed::Tabpage, nvim::Neovim, spawn_local, warn};
 
 #[derive(Default, gtk::CompositeTemplate)]
 #[template(resource = "/com/github/vhakulinen/gnvim/tab.ui")]
@@ -44,21 +44,16 @@ impl ObjectImpl for Tab {
             .connect_pressed(clone!(@weak obj => move |_, _, _, _| {
                 spawn_local!(async move {
                     let nvim = obj.nvim();
-                    let page = obj
-                        .imp()
-                        .tabpage
-                        .borrow();
-                    let page = page
-                        .as_ref()
-                        .expect("tabpage not set");
+                    let page = obj.tabpage().expect("tabpage not set");
                     let res = nvim
                         .client()
+                        .nvim_set_current_tabpage(&page)
                         .await
-                        .nvim_set_current_tabpage(
-                            page
-                        ).await.expect("call to nvim failed");
+                        .expect("call to nvim failed");
 
-                    res.await.expect("nvim_set_current_tabpage failed");
+                    if let Err(err) = res.await {
+                        warn!("nvim_set_current_tabpage failed: {}", err);
+                    }
                 });
             }));
     }
@@ -1,4 +1,4 @@
-use gtk::{glib, subclass::prelude::*};
+use gtk::{glib, prelude::*, subclass::prelude::*};
 
 use crate::{boxed::Tabpage, nvim::Neovim};
 
@@ -16,6 +16,15 @@ impl Tab {
             .expect("Failed to create a Tab")
     }
 
+    /// The tabpage this tab represents.
+    pub fn tabpage(&self) -> Option<nvim::types::Tabpage> {
+        self.imp().tabpage.borrow().as_deref().copied()
+    }
+
+    pub fn set_label(&self, label: &str) {
+        self.set_property("label", label);
+    }
+
     fn nvim(&self) -> Neovim {
         self.imp().nvim.borrow().clone()
     }
@@ -1,10 +1,14 @@
-use futures::lock::Mutex;
-use gio_compat::CompatWrite;
-use gtk::{glib, subclass::prelude::*};
+use std::cell::RefCell;
+
+use gtk::{gio, glib, subclass::prelude::*};
 
 #[derive(Default)]
 pub struct Neovim {
-    pub nvim: Mutex<Option<nvim::Client<CompatWrite>>>,
+    pub nvim: RefCell<Option<nvim::Client>>,
+    /// Server address of neovim, when connected over a socket.
+    pub address: RefCell<Option<String>>,
+    /// The neovim server we spawned, if any.
+    pub server: RefCell<Option<gio::Subprocess>>,
 }
 
 #[glib::object_subclass]
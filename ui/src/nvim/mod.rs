@@ -1,8 +1,19 @@
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
-use futures::lock::{MappedMutexGuard, MutexGuard};
+use futures::channel::oneshot;
+use futures::future::LocalBoxFuture;
+use futures::io::{AsyncRead, AsyncWrite};
+use futures::{ready, FutureExt, StreamExt, TryStreamExt};
 use gio_compat::{CompatRead, CompatWrite};
 use gtk::{gio, glib, prelude::*, subclass::prelude::*};
+use nvim::rpc::record::{Direction, Replay};
+
+use crate::{spawn_local, warn};
 
 mod imp;
 
@@ -11,64 +22,368 @@ glib::wrapper! {
     pub struct Neovim(ObjectSubclass<imp::Neovim>);
 }
 
-pub type MutexGuardedNeovim<'a> =
-    MappedMutexGuard<'a, Option<nvim::Client<CompatWrite>>, nvim::Client<CompatWrite>>;
-
 impl Neovim {
     fn new() -> Self {
         glib::Object::new(&[]).expect("failed to create Neovim")
     }
 
-    /// Locks the internal client for the caller. The lock should not be held
-    /// for too long to allow other parts of the application to access the
-    /// client too.
-    pub async fn client(&self) -> MutexGuardedNeovim<'_> {
-        MutexGuard::map(self.imp().nvim.lock().await, |opt| opt.as_mut().unwrap())
+    /// Returns a handle to the internal client. The handle is cheap to clone.
+    pub fn client(&self) -> nvim::Client {
+        self.imp()
+            .nvim
+            .borrow()
+            .clone()
+            .expect("nvim client not set")
     }
 
-    /// Open the neovim subprocess.
-    ///
-    /// # Arguments
-    ///
-    /// * `args` - Arguments (including the nvim command) for the subprocess.
-    /// * `inherit_fds` - If the fds should be shared with the subprocess. Required
-    /// for the stdin_fd uiattach option.
-    pub fn open(&self, args: &[&OsStr], inherit_fds: bool) -> CompatRead {
-        let mut flags = gio::SubprocessFlags::empty();
-        flags.insert(gio::SubprocessFlags::STDIN_PIPE);
-        flags.insert(gio::SubprocessFlags::STDOUT_PIPE);
+    /// Returns the server address of neovim, if we're connected to it over
+    /// a socket.
+    pub fn address(&self) -> Option<String> {
+        self.imp().address.borrow().clone()
+    }
 
-        if inherit_fds {
-            flags.insert(gio::SubprocessFlags::INHERIT_FDS);
+    /// Stops the neovim process, if we spawned it as a server. Neovim gets
+    /// SIGTERM so that it exits cleanly (e.g. writes the shada file), and
+    /// is killed only on platforms without signals.
+    pub fn kill(&self) {
+        if let Some(p) = self.imp().server.borrow().as_ref() {
+            cfg_if::cfg_if! {
+                if #[cfg(unix)] {
+                    p.send_signal(libc::SIGTERM);
+                } else {
+                    p.force_exit();
+                }
+            }
         }
+    }
 
-        let p = gio::Subprocess::newv(args, flags).expect("failed to open nvim subprocess");
-
-        let writer: CompatWrite = p
-            .stdin_pipe()
-            .expect("get stdin pipe")
-            .dynamic_cast::<gio::PollableOutputStream>()
-            .expect("cast to PollableOutputStream")
-            .into_async_write()
-            .expect("convert to async write")
-            .into();
-
-        let reader: CompatRead = p
-            .stdout_pipe()
-            .expect("get stdout pipe")
-            .dynamic_cast::<gio::PollableInputStream>()
-            .expect("cast to PollableInputStream")
-            .into_async_read()
-            .expect("covert to async read")
-            .into();
-
+    /// Open the connection to neovim.
+    pub fn open(&self, transport: &Transport) -> Result<Reader, glib::Error> {
         let imp = self.imp();
-        imp.nvim
-            .try_lock()
-            .expect("nvim already set")
-            .replace(nvim::Client::new(writer));
+        let (reader, writer): (Reader, Writer) = match transport {
+            Transport::Embed { args, inherit_fds } => boxed(open_embed(args, *inherit_fds)?),
+            Transport::Listen {
+                args,
+                address,
+                inherit_fds,
+            } => {
+                let mut flags = gio::SubprocessFlags::empty();
+                if *inherit_fds {
+                    flags.insert(gio::SubprocessFlags::INHERIT_FDS);
+                }
+
+                let args: Vec<&OsStr> = args.iter().map(|a| a.as_ref()).collect();
+                imp.server
+                    .replace(Some(gio::Subprocess::newv(&args, flags)?));
+                imp.address.replace(Some(address.clone()));
+
+                let connectable = connectable(address)?;
+                deferred(async move { connect_spawned(&connectable).await })
+            }
+            Transport::Socket {
+                address,
+                connectable,
+            } => {
+                imp.address.replace(Some(address.clone()));
+                let connectable = connectable.clone();
+                deferred(async move { gio::SocketClient::new().connect_future(&connectable).await })
+            }
+            Transport::Replay(path) => {
+                let file = std::fs::File::open(path)
+                    .map_err(|err| glib::Error::new(gio::IOErrorEnum::Failed, &err.to_string()))?;
+
+                // NOTE: There is no one to respond to our requests
+                // during the replay, so they're just swallowed.
+                let reader: Reader = Box::new(replay(file));
+                let writer: Writer = Box::new(futures::io::sink());
+                (reader, writer)
+            }
+        };
+
+        let (client, writer_task) = nvim::Client::new(writer);
+        spawn_local!(async move {
+            if let Err(err) = writer_task.await {
+                warn!("nvim writer failed: {}", err);
+            }
+        });
+
+        assert!(imp.nvim.replace(Some(client)).is_none(), "nvim already set");
+
+        Ok(reader)
+    }
+}
+
+/// Reading half of the connection to neovim.
+pub type Reader = Box<dyn AsyncRead + Unpin>;
+/// Writing half of the connection to neovim.
+type Writer = Box<dyn AsyncWrite + Unpin>;
+
+fn boxed((reader, writer): (CompatRead, CompatWrite)) -> (Reader, Writer) {
+    (Box::new(reader), Box::new(writer))
+}
+
+/// Feeds the incoming messages of a recording (see `--record`) to the
+/// returned reader, with the recorded timing.
+fn replay(file: std::fs::File) -> impl AsyncRead + Unpin {
+    let entries = Replay::new(std::io::BufReader::new(file));
+
+    futures::stream::unfold(
+        (entries, Duration::ZERO),
+        |(mut entries, mut prev)| async move {
+            loop {
+                let entry = match entries.next()? {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        warn!("failed to read replay: {}", err);
+                        return None;
+                    }
+                };
+
+                if entry.direction != Direction::Incoming {
+                    continue;
+                }
 
-        reader
+                let time = entry.time();
+                glib::timeout_future(time.saturating_sub(prev)).await;
+                prev = time;
+
+                let msg = entry
+                    .message
+                    .to_vec()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+
+                return Some((msg, (entries, prev)));
+            }
+        },
+    )
+    // NOTE: Keep the ui around once the replay is done, instead of
+    // closing it like when nvim exits.
+    .chain(futures::stream::pending())
+    .boxed_local()
+    .into_async_read()
+}
+
+/// How many times connecting to the server we spawned is tried.
+const CONNECT_ATTEMPTS: usize = 100;
+/// Delay between the connection attempts.
+const CONNECT_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Connects to the server we spawned. Nvim creates the socket only after it
+/// has started, so we'll have to try until it is there.
+async fn connect_spawned(
+    connectable: &gio::SocketConnectable,
+) -> Result<gio::SocketConnection, glib::Error> {
+    let client = gio::SocketClient::new();
+    let mut attempts = 0;
+    loop {
+        match client.connect_future(connectable).await {
+            Ok(conn) => return Ok(conn),
+            Err(_) if attempts < CONNECT_ATTEMPTS => {
+                attempts += 1;
+                glib::timeout_future(CONNECT_INTERVAL).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Opens the connection lazily so that connecting doesn't block the main
+/// loop. The connection is opened by the io loop once it starts reading, and
+/// the writer waits for the reader to hand over its half of the connection.
+fn deferred<F>(connect: F) -> (Reader, Writer)
+where
+    F: Future<Output = Result<gio::SocketConnection, glib::Error>> + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    let reader = Deferred::new(async move {
+        let conn = connect.await.map_err(io_error)?;
+        let (reader, writer) = split(&conn).map_err(io_error)?;
+        // The writer is gone only if the client is.
+        let _ = tx.send(writer);
+
+        Ok(reader)
+    });
+    let writer = Deferred::new(async move {
+        rx.await.map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "failed to connect to nvim",
+            )
+        })
+    });
+
+    (Box::new(reader), Box::new(writer))
+}
+
+fn io_error(err: glib::Error) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, err.to_string())
+}
+
+/// Reader or writer that is opened asynchronously, when it's first used.
+enum Deferred<T> {
+    Opening(LocalBoxFuture<'static, std::io::Result<T>>),
+    Open(T),
+}
+
+impl<T: Unpin> Deferred<T> {
+    fn new<F>(open: F) -> Self
+    where
+        F: Future<Output = std::io::Result<T>> + 'static,
+    {
+        Self::Opening(open.boxed_local())
+    }
+
+    fn poll_open(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<&mut T>> {
+        if let Self::Opening(open) = self {
+            let inner = ready!(open.as_mut().poll(cx))?;
+            *self = Self::Open(inner);
+        }
+
+        match self {
+            Self::Open(inner) => Poll::Ready(Ok(inner)),
+            Self::Opening(_) => unreachable!(),
+        }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for Deferred<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let inner = ready!(self.get_mut().poll_open(cx))?;
+        Pin::new(inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for Deferred<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let inner = ready!(self.get_mut().poll_open(cx))?;
+        Pin::new(inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let inner = ready!(self.get_mut().poll_open(cx))?;
+        Pin::new(inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let inner = ready!(self.get_mut().poll_open(cx))?;
+        Pin::new(inner).poll_close(cx)
+    }
+}
+
+fn split(conn: &gio::SocketConnection) -> Result<(CompatRead, CompatWrite), glib::Error> {
+    gio_compat::split(conn)
+        .map_err(|err| glib::Error::new(gio::IOErrorEnum::NotSupported, &err.to_string()))
+}
+
+/// Open the neovim subprocess.
+///
+/// # Arguments
+///
+/// * `args` - Arguments (including the nvim command) for the subprocess.
+/// * `inherit_fds` - If the fds should be shared with the subprocess. Required
+/// for the stdin_fd uiattach option.
+fn open_embed(
+    args: &[OsString],
+    inherit_fds: bool,
+) -> Result<(CompatRead, CompatWrite), glib::Error> {
+    let mut flags = gio::SubprocessFlags::empty();
+    flags.insert(gio::SubprocessFlags::STDIN_PIPE);
+    flags.insert(gio::SubprocessFlags::STDOUT_PIPE);
+
+    if inherit_fds {
+        flags.insert(gio::SubprocessFlags::INHERIT_FDS);
+    }
+
+    let args: Vec<&OsStr> = args.iter().map(|a| a.as_ref()).collect();
+    let p = gio::Subprocess::newv(&args, flags)?;
+
+    let writer: CompatWrite = p
+        .stdin_pipe()
+        .expect("get stdin pipe")
+        .dynamic_cast::<gio::PollableOutputStream>()
+        .expect("cast to PollableOutputStream")
+        .into_async_write()
+        .expect("convert to async write")
+        .into();
+
+    let reader: CompatRead = p
+        .stdout_pipe()
+        .expect("get stdout pipe")
+        .dynamic_cast::<gio::PollableInputStream>()
+        .expect("cast to PollableInputStream")
+        .into_async_read()
+        .expect("covert to async read")
+        .into();
+
+    Ok((reader, writer))
+}
+
+/// How to connect to neovim.
+#[derive(Debug, Clone)]
+pub enum Transport {
+    /// Spawn `nvim --embed` and talk to it over its stdin and stdout.
+    Embed {
+        /// Arguments (including the nvim command) for the subprocess.
+        args: Vec<OsString>,
+        /// If the fds should be shared with the subprocess.
+        inherit_fds: bool,
+    },
+    /// Spawn neovim as a server (`nvim --headless --listen`), and connect to
+    /// it. Unlike with `Embed`, neovim can outlive us if we detach from it.
+    Listen {
+        /// Arguments (including the nvim command) for the subprocess.
+        args: Vec<OsString>,
+        /// Address neovim is listening on.
+        address: String,
+        /// If the fds should be shared with the subprocess.
+        inherit_fds: bool,
+    },
+    /// Connect to a running neovim server (see `:help --listen`).
+    Socket {
+        address: String,
+        connectable: gio::SocketConnectable,
+    },
+    /// Replay a recording (see `--record`) without neovim.
+    Replay(PathBuf),
+}
+
+impl Transport {
+    /// Parses a server address.
+    pub fn server(address: &str) -> Result<Self, glib::Error> {
+        Ok(Self::Socket {
+            address: address.to_string(),
+            connectable: connectable(address)?,
+        })
+    }
+}
+
+/// Parses a server address (see `nvim::transport::is_tcp_address`).
+fn connectable(address: &str) -> Result<gio::SocketConnectable, glib::Error> {
+    if nvim::transport::is_tcp_address(address) {
+        Ok(gio::NetworkAddress::parse(address, 0)?.upcast())
+    } else {
+        unix_socket(address)
+    }
+}
+
+fn unix_socket(path: &str) -> Result<gio::SocketConnectable, glib::Error> {
+    cfg_if::cfg_if! {
+        if #[cfg(unix)] {
+            return Ok(gio::UnixSocketAddress::new(std::path::Path::new(path)).upcast());
+        } else {
+            return Err(glib::Error::new(
+                gio::IOErrorEnum::NotSupported,
+                "unix sockets not supported on this platform",
+            ));
+        }
     }
 }
 